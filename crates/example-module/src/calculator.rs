@@ -0,0 +1,137 @@
+//! Checked arithmetic backing the calculator call messages.
+//!
+//! Everything in here is pure integer arithmetic so that the native node and the zk guests
+//! always agree on the outcome of an operation.
+use anyhow::Context;
+use sov_modules_api::macros::UniversalWallet;
+#[cfg(feature = "native")]
+use sov_modules_api::schemars;
+
+/// Maximum number of operations in a [`crate::CallMessage::Batch`].
+pub const MAX_BATCH_LEN: usize = 32;
+
+/// The arithmetic operations supported by the calculator.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "arbitrary",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    UniversalWallet,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// `lhs + rhs`
+    Add,
+    /// `lhs - rhs`
+    Sub,
+    /// `lhs * rhs`
+    Mul,
+    /// `lhs / rhs`, rounding towards zero.
+    Div,
+    /// `lhs % rhs`
+    Mod,
+    /// `lhs ^ rhs`. The exponent must fit into a `u32`.
+    Pow,
+}
+
+/// A single step of a [`crate::CallMessage::Batch`].
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "arbitrary",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    UniversalWallet,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct Op {
+    /// The operation to apply.
+    pub operation: Operation,
+    /// The right-hand side of the operation.
+    pub operand: u64,
+}
+
+/// Errors raised when an [`Operation`] cannot be applied.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CalculatorError {
+    /// The result does not fit into the register.
+    #[error("{operation:?} overflowed with operands {lhs} and {rhs}")]
+    Overflow {
+        /// The operation that overflowed.
+        operation: Operation,
+        /// The value of the register.
+        lhs: u64,
+        /// The operand supplied by the caller.
+        rhs: u64,
+    },
+    /// `Div` or `Mod` was called with a zero operand.
+    #[error("{operation:?} by zero")]
+    DivisionByZero {
+        /// The operation that was attempted.
+        operation: Operation,
+    },
+    /// A batch was empty or longer than [`MAX_BATCH_LEN`].
+    #[error("A batch must contain between 1 and {max} operations, got {len}", max = MAX_BATCH_LEN)]
+    InvalidBatchLength {
+        /// The number of operations in the rejected batch.
+        len: usize,
+    },
+}
+
+impl Operation {
+    /// Applies the operation to `lhs` and `rhs`, returning an error instead of wrapping or panicking.
+    pub fn apply(self, lhs: u64, rhs: u64) -> Result<u64, CalculatorError> {
+        let overflow = CalculatorError::Overflow {
+            operation: self,
+            lhs,
+            rhs,
+        };
+
+        match self {
+            Operation::Add => lhs.checked_add(rhs).ok_or(overflow),
+            Operation::Sub => lhs.checked_sub(rhs).ok_or(overflow),
+            Operation::Mul => lhs.checked_mul(rhs).ok_or(overflow),
+            Operation::Div | Operation::Mod if rhs == 0 => {
+                Err(CalculatorError::DivisionByZero { operation: self })
+            }
+            Operation::Div => Ok(lhs / rhs),
+            Operation::Mod => Ok(lhs % rhs),
+            Operation::Pow => u32::try_from(rhs)
+                .ok()
+                .and_then(|exp| lhs.checked_pow(exp))
+                .ok_or(overflow),
+        }
+    }
+}
+
+/// Applies `ops` in order, starting from `initial`. Stops at the first failing step.
+pub fn apply_batch(initial: u64, ops: &[Op]) -> anyhow::Result<u64> {
+    if ops.is_empty() || ops.len() > MAX_BATCH_LEN {
+        return Err(CalculatorError::InvalidBatchLength { len: ops.len() }.into());
+    }
+
+    ops.iter().enumerate().try_fold(initial, |acc, (step, op)| {
+        op.operation
+            .apply(acc, op.operand)
+            .with_context(|| format!("Step {step} of the batch failed"))
+    })
+}
@@ -1,20 +1,32 @@
 use std::fmt::Debug;
 
 use anyhow::Result;
+use sov_modules_api::macros::config_value;
 use sov_modules_api::macros::UniversalWallet;
 use sov_modules_api::prelude::*;
 #[cfg(feature = "native")]
 use sov_modules_api::schemars;
-use sov_modules_api::{CallResponse, Context, EventEmitter, Spec, TxState};
+use sov_modules_api::{CallResponse, Context, EventEmitter, GasArray, Spec, TxState};
 
+use crate::calculator::{apply_batch, Op, Operation};
+use crate::error::AccessError;
 use crate::event::Event;
+use crate::fee::FeeConfig;
+use crate::vm::{evaluate, Instruction, VmError};
 use crate::ExampleModule;
 
 /// This enumeration represents the available call messages for interacting with
 /// the `ExampleModule` module.
 /// The `derive` for [`schemars::JsonSchema`] is a requirement of
 /// [`sov_modules_api::ModuleCallJsonSchema`].
-#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(
+        bound = "S::Address: sov_modules_api::schemars::JsonSchema",
+        rename = "CallMessage"
+    )
+)]
 #[cfg_attr(
     feature = "arbitrary",
     derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
@@ -30,8 +42,48 @@ use crate::ExampleModule;
     serde::Deserialize,
 )]
 #[serde(rename_all = "snake_case")]
-pub enum CallMessage {
+pub enum CallMessage<S: Spec> {
+    /// Sets the shared value. Only the owner and the writers can call it.
     SetValue(u32),
+    /// Hands the ownership of the module over to `new_owner`. Only the owner can call it.
+    TransferOwnership {
+        /// The address of the new owner.
+        new_owner: S::Address,
+    },
+    /// Allows `writer` to set the shared value. Only the owner can call it.
+    AddWriter {
+        /// The address to grant the writer role to.
+        writer: S::Address,
+    },
+    /// Revokes the writer role from `writer`. Only the owner can call it.
+    RemoveWriter {
+        /// The address to revoke the writer role from.
+        writer: S::Address,
+    },
+    /// Replaces the fee charged for value updates and calculator operations.
+    /// `None` makes them free. Only the owner can call it.
+    SetFee {
+        /// The new fee.
+        fee: Option<FeeConfig<S>>,
+    },
+    /// Adds the operand to the sender's register.
+    Add(u64),
+    /// Subtracts the operand from the sender's register.
+    Sub(u64),
+    /// Multiplies the sender's register by the operand.
+    Mul(u64),
+    /// Divides the sender's register by the operand.
+    Div(u64),
+    /// Replaces the sender's register with the remainder of its division by the operand.
+    Mod(u64),
+    /// Raises the sender's register to the power of the operand.
+    Pow(u32),
+    /// Applies up to [`crate::MAX_BATCH_LEN`] operations to the sender's register in a
+    /// single transaction. If any of them fails, none of them is applied.
+    Batch(Vec<Op>),
+    /// Evaluates a postfix program of up to [`crate::MAX_PROGRAM_LEN`] instructions and
+    /// stores its result in the sender's register. Gas is charged for every instruction.
+    Evaluate(Vec<Instruction>),
 }
 
 impl<S: Spec> ExampleModule<S> {
@@ -39,12 +91,211 @@ impl<S: Spec> ExampleModule<S> {
     pub(crate) fn set_value(
         &self,
         new_value: u32,
-        _context: &Context<S>,
+        context: &Context<S>,
         state: &mut impl TxState<S>,
     ) -> Result<CallResponse> {
+        self.ensure_writer(context.sender(), state)?;
+        self.charge_fee(1, context, state)?;
+
         self.value.set(&new_value, state)?;
+        self.record_value_change(new_value, context, state)?;
         self.emit_event(state, Event::Set { value: new_value });
 
         Ok(CallResponse::default())
     }
+
+    /// Applies `operation` to the sender's register. Registers start at zero.
+    pub(crate) fn compute(
+        &self,
+        operation: Operation,
+        operand: u64,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        self.charge_fee(1, context, state)?;
+
+        let account = context.sender();
+        let current = self.registers.get(account, state)?.unwrap_or_default();
+        let result = operation.apply(current, operand)?;
+
+        self.registers.set(account, &result, state)?;
+        self.emit_event(
+            state,
+            Event::Computed {
+                account: account.clone(),
+                operation,
+                operand,
+                result,
+            },
+        );
+
+        Ok(CallResponse::default())
+    }
+
+    /// Applies every operation of `ops` to the sender's register, or none of them.
+    pub(crate) fn compute_batch(
+        &self,
+        ops: Vec<Op>,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        let account = context.sender();
+        let initial = self.registers.get(account, state)?.unwrap_or_default();
+        // The whole batch is evaluated before touching the state, so a failing step leaves
+        // the register as it was.
+        let result = apply_batch(initial, &ops)?;
+
+        self.charge_fee(ops.len() as u64, context, state)?;
+        self.registers.set(account, &result, state)?;
+        self.emit_event(
+            state,
+            Event::BatchComputed {
+                account: account.clone(),
+                operations: ops.len() as u64,
+                initial,
+                result,
+            },
+        );
+
+        Ok(CallResponse::default())
+    }
+
+    /// Evaluates `program` and stores its result in the sender's register.
+    pub(crate) fn evaluate_program(
+        &self,
+        program: Vec<Instruction>,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        // Programs cannot jump, so every instruction is charged upfront.
+        let gas_per_instruction =
+            S::Gas::from_slice(&config_value!("EXAMPLE_MODULE_GAS_PER_VM_INSTRUCTION"));
+        for _ in 0..program.len().min(crate::MAX_PROGRAM_LEN) {
+            state.charge_gas(&gas_per_instruction)?;
+        }
+
+        self.charge_fee(1, context, state)?;
+
+        let account = context.sender();
+        let register = self.registers.get(account, state)?.unwrap_or_default();
+        let result = evaluate(&program, register)?;
+        let result = u64::try_from(result).map_err(|_| VmError::ResultOutOfRange { result })?;
+
+        self.registers.set(account, &result, state)?;
+        self.emit_event(
+            state,
+            Event::Evaluated {
+                account: account.clone(),
+                instructions: program.len() as u64,
+                result,
+            },
+        );
+
+        Ok(CallResponse::default())
+    }
+
+    /// Transfers the ownership of the module to `new_owner`.
+    pub(crate) fn transfer_ownership(
+        &self,
+        new_owner: S::Address,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        let previous_owner = self.ensure_owner(context.sender(), state)?;
+
+        self.owner.set(&new_owner, state)?;
+        self.emit_event(
+            state,
+            Event::OwnershipTransferred {
+                previous_owner,
+                new_owner,
+            },
+        );
+
+        Ok(CallResponse::default())
+    }
+
+    /// Grants the writer role to `writer`.
+    pub(crate) fn add_writer(
+        &self,
+        writer: S::Address,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        self.ensure_owner(context.sender(), state)?;
+
+        if self.writers.get(&writer, state)?.is_some() {
+            return Err(AccessError::AlreadyWriter { writer }.into());
+        }
+
+        self.writers.set(&writer, &(), state)?;
+        self.emit_event(state, Event::WriterAdded { writer });
+
+        Ok(CallResponse::default())
+    }
+
+    /// Revokes the writer role from `writer`.
+    pub(crate) fn remove_writer(
+        &self,
+        writer: S::Address,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        self.ensure_owner(context.sender(), state)?;
+
+        if self.writers.remove(&writer, state)?.is_none() {
+            return Err(AccessError::UnknownWriter { writer }.into());
+        }
+
+        self.emit_event(state, Event::WriterRemoved { writer });
+
+        Ok(CallResponse::default())
+    }
+
+    /// Replaces the fee configuration.
+    pub(crate) fn set_fee(
+        &self,
+        fee: Option<FeeConfig<S>>,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        self.ensure_owner(context.sender(), state)?;
+
+        if let Some(fee) = &fee {
+            self.validate_fee(fee, state)?;
+        }
+
+        match &fee {
+            Some(fee) => self.fee_config.set(fee, state)?,
+            None => self.fee_config.delete(state)?,
+        }
+        self.emit_event(state, Event::FeeUpdated { fee });
+
+        Ok(CallResponse::default())
+    }
+
+    /// Returns the current owner if it is `sender`, fails with [`AccessError::NotOwner`] otherwise.
+    fn ensure_owner(&self, sender: &S::Address, state: &mut impl TxState<S>) -> Result<S::Address> {
+        match self.owner.get(state)? {
+            Some(owner) if &owner == sender => Ok(owner),
+            _ => Err(AccessError::NotOwner {
+                sender: sender.clone(),
+            }
+            .into()),
+        }
+    }
+
+    /// Fails with [`AccessError::NotWriter`] unless `sender` is the owner or a writer.
+    fn ensure_writer(&self, sender: &S::Address, state: &mut impl TxState<S>) -> Result<()> {
+        if self.owner.get(state)?.as_ref() == Some(sender)
+            || self.writers.get(sender, state)?.is_some()
+        {
+            return Ok(());
+        }
+
+        Err(AccessError::NotWriter {
+            sender: sender.clone(),
+        }
+        .into())
+    }
 }
@@ -0,0 +1,30 @@
+use std::fmt::Display;
+
+/// Errors raised when a caller is not allowed to perform a privileged call.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AccessError<Address: Display> {
+    /// Only the owner can manage ownership and writers.
+    #[error("{sender} is not the owner of the module")]
+    NotOwner {
+        /// The sender of the rejected call.
+        sender: Address,
+    },
+    /// Only the owner and the writers can set the shared value.
+    #[error("{sender} is not allowed to set the value")]
+    NotWriter {
+        /// The sender of the rejected call.
+        sender: Address,
+    },
+    /// The address is already a writer.
+    #[error("{writer} is already a writer")]
+    AlreadyWriter {
+        /// The address passed to `AddWriter`.
+        writer: Address,
+    },
+    /// The address is not a writer.
+    #[error("{writer} is not a writer")]
+    UnknownWriter {
+        /// The address passed to `RemoveWriter`.
+        writer: Address,
+    },
+}
@@ -1,4 +1,10 @@
-/// Template Event
+use sov_bank::TokenId;
+use sov_modules_api::Spec;
+
+use crate::calculator::Operation;
+use crate::fee::FeeConfig;
+
+/// Events emitted by the `ExampleModule`.
 #[derive(
     borsh::BorshDeserialize,
     borsh::BorshSerialize,
@@ -8,8 +14,62 @@
     PartialEq,
     Clone,
 )]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
 #[serde(rename_all = "snake_case")]
-pub enum Event {
-    /// Template event set value
+pub enum Event<S: Spec> {
+    /// The shared value was set.
     Set { value: u32 },
+    /// An operation was applied to an account's register.
+    Computed {
+        /// The owner of the register.
+        account: S::Address,
+        /// The operation that was applied.
+        operation: Operation,
+        /// The operand supplied by the caller.
+        operand: u64,
+        /// The new value of the register.
+        result: u64,
+    },
+    /// A batch of operations was applied to an account's register.
+    BatchComputed {
+        /// The owner of the register.
+        account: S::Address,
+        /// The number of operations in the batch.
+        operations: u64,
+        /// The value of the register before the batch.
+        initial: u64,
+        /// The value of the register after the batch.
+        result: u64,
+    },
+    /// A program was evaluated and its result stored in an account's register.
+    Evaluated {
+        /// The owner of the register.
+        account: S::Address,
+        /// The number of instructions in the program.
+        instructions: u64,
+        /// The new value of the register.
+        result: u64,
+    },
+    /// The ownership of the module changed hands.
+    OwnershipTransferred {
+        /// The owner before the transfer.
+        previous_owner: S::Address,
+        /// The owner after the transfer.
+        new_owner: S::Address,
+    },
+    /// An address was granted the writer role.
+    WriterAdded { writer: S::Address },
+    /// An address lost the writer role.
+    WriterRemoved { writer: S::Address },
+    /// A fee was charged to the sender of a call.
+    FeeCharged {
+        /// The account that paid the fee.
+        payer: S::Address,
+        /// The token the fee was paid in.
+        token_id: TokenId,
+        /// The amount that was charged.
+        amount: u64,
+    },
+    /// The owner changed the fee. `None` means calls are free.
+    FeeUpdated { fee: Option<FeeConfig<S>> },
 }
@@ -0,0 +1,135 @@
+use anyhow::{bail, Result};
+use session_keys::ActiveSession;
+use sov_bank::{Coins, TokenId};
+use sov_modules_api::macros::UniversalWallet;
+#[cfg(feature = "native")]
+use sov_modules_api::schemars;
+use sov_modules_api::{Context, EventEmitter, Spec, StateAccessor, TxState};
+
+use crate::event::Event;
+use crate::ExampleModule;
+
+/// Where the fees collected by the module end up.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(
+        bound = "S::Address: sov_modules_api::schemars::JsonSchema",
+        rename = "FeeRecipient"
+    )
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Clone,
+    UniversalWallet,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[serde(rename_all = "snake_case")]
+pub enum FeeRecipient<S: Spec> {
+    /// Fees are transferred to the given address.
+    Treasury(S::Address),
+    /// Fees are burned.
+    Burn,
+}
+
+/// The fee charged for every value update and calculator operation.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(
+        bound = "S::Address: sov_modules_api::schemars::JsonSchema",
+        rename = "FeeConfig"
+    )
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Clone,
+    UniversalWallet,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[serde(rename_all = "snake_case")]
+pub struct FeeConfig<S: Spec> {
+    /// The token the fee is paid in.
+    pub token_id: TokenId,
+    /// The amount charged per call.
+    pub amount: u64,
+    /// Who receives the fee.
+    pub recipient: FeeRecipient<S>,
+}
+
+impl<S: Spec> ExampleModule<S> {
+    /// Rejects a fee which can't be charged, in genesis and in `CallMessage::SetFee` alike.
+    pub(crate) fn validate_fee(
+        &self,
+        fee: &FeeConfig<S>,
+        state: &mut impl StateAccessor,
+    ) -> Result<()> {
+        if fee.amount == 0 {
+            bail!("The fee amount must not be zero, omit the fee to make calls free");
+        }
+        if self
+            .bank
+            .get_total_supply_of(&fee.token_id, state)?
+            .is_none()
+        {
+            bail!("Fee token {} does not exist", fee.token_id);
+        }
+        Ok(())
+    }
+
+    /// Charges the configured fee, if any, to the sender of the call, once per operation. The
+    /// fees of calls sent with a session key count against its spend limit.
+    pub(crate) fn charge_fee(
+        &self,
+        operations: u64,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        let Some(fee) = self.fee_config.get(state)? else {
+            return Ok(());
+        };
+
+        let payer = context.sender();
+        let amount = fee
+            .amount
+            .checked_mul(operations)
+            .ok_or_else(|| anyhow::anyhow!("Fee for {operations} operations overflows"))?;
+        if let Some(session) = context.get_sender_credentials().get::<ActiveSession<S>>() {
+            self.session_keys.spend(session, amount, state)?;
+        }
+        let coins = Coins {
+            amount,
+            token_id: fee.token_id,
+        };
+
+        match &fee.recipient {
+            FeeRecipient::Treasury(treasury) => {
+                self.bank.transfer_from(payer, treasury, coins, state)?;
+            }
+            FeeRecipient::Burn => {
+                self.bank.burn_from(payer, &coins, state)?;
+            }
+        }
+
+        self.emit_event(
+            state,
+            Event::FeeCharged {
+                payer: payer.clone(),
+                token_id: fee.token_id,
+                amount,
+            },
+        );
+
+        Ok(())
+    }
+}
@@ -1,14 +1,86 @@
-use anyhow::Result;
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
 use sov_modules_api::{GenesisState, Module, Spec};
 
-use crate::ExampleModule;
+use crate::{ExampleModule, ValueChange};
+
+/// The height recorded in the value history for the initial value.
+const GENESIS_HEIGHT: u64 = 0;
 
 impl<S: Spec> ExampleModule<S> {
     pub(crate) fn init_module(
         &self,
-        _config: &<Self as Module>::Config,
-        _state: &mut impl GenesisState<S>,
+        config: &<Self as Module>::Config,
+        state: &mut impl GenesisState<S>,
+    ) -> Result<()> {
+        self.validate_config(config, state)?;
+
+        self.owner.set(&config.owner, state)?;
+
+        for writer in &config.writers {
+            self.writers.set(writer, &(), state)?;
+        }
+
+        if let Some(value) = config.initial_value {
+            self.value.set(&value, state)?;
+            self.value_history.push(
+                &ValueChange {
+                    value,
+                    height: GENESIS_HEIGHT,
+                },
+                state,
+            )?;
+        }
+
+        if let Some(fee) = &config.fee {
+            self.fee_config.set(fee, state)?;
+        }
+
+        for (account, value) in &config.registers {
+            self.registers.set(account, value, state)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks the config before anything is written, so that a bad genesis file is rejected
+    /// with an explicit error instead of producing an unusable module.
+    fn validate_config(
+        &self,
+        config: &<Self as Module>::Config,
+        state: &mut impl GenesisState<S>,
     ) -> Result<()> {
+        let mut writers = HashSet::new();
+        for writer in &config.writers {
+            if writer == &config.owner {
+                bail!(
+                    "The owner {} must not be listed as a writer, it can always set the value",
+                    writer
+                );
+            }
+            if !writers.insert(writer) {
+                bail!(
+                    "Writer {} is listed more than once in the genesis config",
+                    writer
+                );
+            }
+        }
+
+        let mut accounts = HashSet::new();
+        for (account, _) in &config.registers {
+            if !accounts.insert(account) {
+                bail!(
+                    "The register of {} is set more than once in the genesis config",
+                    account
+                );
+            }
+        }
+
+        if let Some(fee) = &config.fee {
+            self.validate_fee(fee, state)?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use sov_modules_api::{Context, Spec, TxState};
+
+use crate::ExampleModule;
+
+/// An update of the shared value, kept in accessory state.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct ValueChange {
+    /// The new value.
+    pub value: u32,
+    /// The visible slot number at which the value was set.
+    pub height: u64,
+}
+
+impl<S: Spec> ExampleModule<S> {
+    /// Appends `value` to the value history. Accessory state is not part of the state root,
+    /// so the history is only available on native nodes and does not make proofs bigger.
+    pub(crate) fn record_value_change(
+        &self,
+        value: u32,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<()> {
+        let change = ValueChange {
+            value,
+            height: context.visible_slot_number().get(),
+        };
+        self.value_history.push(&change, state)?;
+
+        Ok(())
+    }
+}
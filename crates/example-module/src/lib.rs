@@ -1,21 +1,49 @@
+mod calculator;
 mod call;
+mod error;
 mod event;
+mod fee;
 mod genesis;
+mod history;
 #[cfg(feature = "native")]
 mod query;
+mod vm;
+pub use calculator::{apply_batch, CalculatorError, Op, Operation, MAX_BATCH_LEN};
 pub use call::CallMessage;
+pub use error::AccessError;
 pub use event::Event;
+pub use fee::{FeeConfig, FeeRecipient};
+pub use history::ValueChange;
 #[cfg(feature = "native")]
 pub use query::*;
 use serde::{Deserialize, Serialize};
 use sov_modules_api::{
-    CallResponse, Context, Error, GenesisState, Module, ModuleId, ModuleInfo, ModuleRestApi, Spec,
-    StateValue, TxState,
+    AccessoryStateVec, CallResponse, Context, Error, GenesisState, Module, ModuleId, ModuleInfo,
+    ModuleRestApi, Spec, StateMap, StateValue, TxState,
 };
+pub use vm::{evaluate, Instruction, VmError, MAX_PROGRAM_LEN, MAX_STACK_DEPTH};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Genesis configuration of the [`ExampleModule`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "S::Address: Serialize + serde::de::DeserializeOwned")]
 #[serde(rename_all = "snake_case")]
-pub struct ExampleModuleConfig {}
+pub struct ExampleModuleConfig<S: Spec> {
+    /// The address allowed to manage writers and transfer ownership.
+    pub owner: S::Address,
+    /// Addresses allowed to set the shared value, in addition to the owner.
+    #[serde(default)]
+    pub writers: Vec<S::Address>,
+    /// The value stored in the module at genesis, if any.
+    #[serde(default)]
+    pub initial_value: Option<u32>,
+    /// The fee charged for value updates and calculator operations. Calls are free if unset.
+    #[serde(default)]
+    pub fee: Option<FeeConfig<S>>,
+    /// The initial calculator registers, as `[address, value]` pairs. Accounts not listed
+    /// start with a register of zero.
+    #[serde(default)]
+    pub registers: Vec<(S::Address, u64)>,
+}
 
 /// A new module:
 /// - Must derive `ModuleInfo`
@@ -32,19 +60,44 @@ pub struct ExampleModule<S: Spec> {
     #[state]
     pub value: StateValue<u32>,
 
-    /// Reference to the Bank module.
+    /// Every update of `value`, in order. Kept in accessory state so it does not bloat the proof.
+    #[state]
+    pub value_history: AccessoryStateVec<ValueChange>,
+
+    /// The address allowed to manage the writers.
+    #[state]
+    pub owner: StateValue<S::Address>,
+
+    /// Addresses allowed to set `value`, in addition to the owner.
+    #[state]
+    pub writers: StateMap<S::Address, ()>,
+
+    /// The fee charged for value updates and calculator operations, if any.
+    #[state]
+    pub fee_config: StateValue<FeeConfig<S>>,
+
+    /// Calculator registers, one per account.
+    #[state]
+    pub registers: StateMap<S::Address, u64>,
+
+    /// Reference to the Bank module, used to collect fees.
     #[module]
-    pub(crate) _bank: sov_bank::Bank<S>,
+    pub(crate) bank: sov_bank::Bank<S>,
+
+    /// Reference to the SessionKeys module, counting the fees paid by session keys against
+    /// their spend limit.
+    #[module]
+    pub(crate) session_keys: session_keys::SessionKeys<S>,
 }
 
 impl<S: Spec> Module for ExampleModule<S> {
     type Spec = S;
 
-    type Config = ExampleModuleConfig;
+    type Config = ExampleModuleConfig<S>;
 
-    type CallMessage = call::CallMessage;
+    type CallMessage = call::CallMessage<S>;
 
-    type Event = Event;
+    type Event = Event<S>;
 
     fn genesis(
         &self,
@@ -65,6 +118,36 @@ impl<S: Spec> Module for ExampleModule<S> {
             call::CallMessage::SetValue(new_value) => {
                 Ok(self.set_value(new_value, context, state)?)
             }
+            call::CallMessage::TransferOwnership { new_owner } => {
+                Ok(self.transfer_ownership(new_owner, context, state)?)
+            }
+            call::CallMessage::AddWriter { writer } => Ok(self.add_writer(writer, context, state)?),
+            call::CallMessage::RemoveWriter { writer } => {
+                Ok(self.remove_writer(writer, context, state)?)
+            }
+            call::CallMessage::SetFee { fee } => Ok(self.set_fee(fee, context, state)?),
+            call::CallMessage::Add(operand) => {
+                Ok(self.compute(Operation::Add, operand, context, state)?)
+            }
+            call::CallMessage::Sub(operand) => {
+                Ok(self.compute(Operation::Sub, operand, context, state)?)
+            }
+            call::CallMessage::Mul(operand) => {
+                Ok(self.compute(Operation::Mul, operand, context, state)?)
+            }
+            call::CallMessage::Div(operand) => {
+                Ok(self.compute(Operation::Div, operand, context, state)?)
+            }
+            call::CallMessage::Mod(operand) => {
+                Ok(self.compute(Operation::Mod, operand, context, state)?)
+            }
+            call::CallMessage::Pow(exponent) => {
+                Ok(self.compute(Operation::Pow, exponent.into(), context, state)?)
+            }
+            call::CallMessage::Batch(ops) => Ok(self.compute_batch(ops, context, state)?),
+            call::CallMessage::Evaluate(program) => {
+                Ok(self.evaluate_program(program, context, state)?)
+            }
         }
     }
 }
@@ -1,18 +1,173 @@
+use axum::extract::Query;
+use axum::routing::get;
 use sov_modules_api::prelude::UnwrapInfallible;
-use sov_modules_api::ApiStateAccessor;
+use sov_modules_api::rest::utils::{ApiResult, Path};
+use sov_modules_api::rest::{ApiState, HasCustomRestApi};
+use sov_modules_api::{ApiStateAccessor, Spec};
 
-use super::ExampleModule;
+use super::{ExampleModule, ValueChange};
+
+/// Maximum number of history entries returned by a single request.
+pub const MAX_HISTORY_PAGE_SIZE: u64 = 100;
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Eq, PartialEq)]
 pub struct Response {
     pub value: Option<u32>,
 }
 
-impl<S: sov_modules_api::Spec> ExampleModule<S> {
+/// The value of an account's calculator register.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Eq, PartialEq)]
+pub struct RegisterResponse {
+    pub value: u64,
+}
+
+/// Query parameters selecting a page of the value history.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Eq, PartialEq)]
+pub struct HistoryPage {
+    /// Index of the first entry to return.
+    #[serde(default)]
+    pub offset: u64,
+    /// Number of entries to return, capped at [`MAX_HISTORY_PAGE_SIZE`].
+    #[serde(default = "default_page_size")]
+    pub limit: u64,
+}
+
+fn default_page_size() -> u64 {
+    MAX_HISTORY_PAGE_SIZE
+}
+
+/// A page of the value history.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Eq, PartialEq)]
+pub struct ValueHistoryResponse {
+    /// Total number of recorded updates.
+    pub total: u64,
+    /// The requested updates, oldest first.
+    pub entries: Vec<ValueChange>,
+}
+
+/// The value as of a given height.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Eq, PartialEq)]
+pub struct ValueAtHeightResponse {
+    /// The requested height.
+    pub height: u64,
+    /// `None` if the value had not been set yet at `height`.
+    pub value: Option<u32>,
+}
+
+impl<S: Spec> ExampleModule<S> {
     /// Queries the state of the module.
     pub fn query_value(&self, state: &mut ApiStateAccessor<S>) -> Response {
         Response {
             value: self.value.get(state).unwrap_infallible(),
         }
     }
+
+    /// Queries the calculator register of `account`. Accounts that never used the
+    /// calculator have a register of zero.
+    pub fn query_register(
+        &self,
+        account: &S::Address,
+        state: &mut ApiStateAccessor<S>,
+    ) -> RegisterResponse {
+        RegisterResponse {
+            value: self
+                .registers
+                .get(account, state)
+                .unwrap_infallible()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns up to `limit` entries of the value history, starting at `offset`.
+    pub fn query_value_history(
+        &self,
+        page: &HistoryPage,
+        state: &mut ApiStateAccessor<S>,
+    ) -> ValueHistoryResponse {
+        let total = self.value_history.len(state).unwrap_infallible() as u64;
+        let limit = page.limit.min(MAX_HISTORY_PAGE_SIZE);
+        let end = total.min(page.offset.saturating_add(limit));
+
+        let entries = (page.offset..end)
+            .filter_map(|index| {
+                self.value_history
+                    .get(index as usize, state)
+                    .unwrap_infallible()
+            })
+            .collect();
+
+        ValueHistoryResponse { total, entries }
+    }
+
+    /// Returns the value as it was after every update made at or before `height`.
+    pub fn query_value_at_height(
+        &self,
+        height: u64,
+        state: &mut ApiStateAccessor<S>,
+    ) -> ValueAtHeightResponse {
+        // Updates are appended in execution order, so their heights are sorted and we can
+        // binary search for the number of updates made at or before `height`.
+        let mut low = 0;
+        let mut high = self.value_history.len(state).unwrap_infallible();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let change = self
+                .value_history
+                .get(mid, state)
+                .unwrap_infallible()
+                .expect("Index is in bounds");
+            if change.height <= height {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        let value = match low {
+            0 => None,
+            count => self
+                .value_history
+                .get(count - 1, state)
+                .unwrap_infallible()
+                .map(|change| change.value),
+        };
+
+        ValueAtHeightResponse { height, value }
+    }
+
+    async fn route_value_history(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+        Query(page): Query<HistoryPage>,
+    ) -> ApiResult<ValueHistoryResponse> {
+        Ok(state.query_value_history(&page, &mut accessor).into())
+    }
+
+    async fn route_value_at_height(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+        Path(height): Path<u64>,
+    ) -> ApiResult<ValueAtHeightResponse> {
+        Ok(state.query_value_at_height(height, &mut accessor).into())
+    }
+
+    async fn route_register(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+        Path(account): Path<S::Address>,
+    ) -> ApiResult<RegisterResponse> {
+        Ok(state.query_register(&account, &mut accessor).into())
+    }
+}
+
+impl<S: Spec> HasCustomRestApi for ExampleModule<S> {
+    type Spec = S;
+
+    fn custom_rest_api(&self, state: ApiState<S>) -> axum::Router<()> {
+        axum::Router::new()
+            .route("/registers/:address", get(Self::route_register))
+            .route("/value/history", get(Self::route_value_history))
+            .route("/value/at-height/:height", get(Self::route_value_at_height))
+            .with_state(state.with(self.clone()))
+    }
 }
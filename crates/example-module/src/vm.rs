@@ -0,0 +1,173 @@
+//! A tiny stack machine evaluating postfix (RPN) programs.
+//!
+//! Programs are straight-line: there are no jumps, so every instruction runs at most once and
+//! the cost of a program is known before it starts. All arithmetic is checked `i128`
+//! arithmetic, which behaves the same on the native node and inside the zkvm.
+use sov_modules_api::macros::UniversalWallet;
+#[cfg(feature = "native")]
+use sov_modules_api::schemars;
+
+use crate::calculator::Operation;
+
+/// Maximum number of instructions in a program.
+pub const MAX_PROGRAM_LEN: usize = 64;
+
+/// Maximum number of values on the stack at any point of the execution.
+pub const MAX_STACK_DEPTH: usize = 16;
+
+/// An instruction of the stack machine.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "arbitrary",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    UniversalWallet,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Instruction {
+    /// Pushes a constant.
+    Push(u64),
+    /// Pushes the sender's register.
+    Load,
+    /// Pops `rhs`, then `lhs`, and pushes `lhs <operation> rhs`.
+    Apply(Operation),
+    /// Negates the top of the stack.
+    Neg,
+    /// Duplicates the top of the stack.
+    Dup,
+    /// Swaps the two topmost values.
+    Swap,
+    /// Discards the top of the stack.
+    Drop,
+}
+
+/// Errors raised while evaluating a program.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum VmError {
+    /// The program is empty or longer than [`MAX_PROGRAM_LEN`].
+    #[error("A program must contain between 1 and {max} instructions, got {len}", max = MAX_PROGRAM_LEN)]
+    InvalidProgramLength {
+        /// The number of instructions in the rejected program.
+        len: usize,
+    },
+    /// An instruction needed more values than there were on the stack.
+    #[error("Stack underflow at instruction {pc}")]
+    StackUnderflow {
+        /// Index of the failing instruction.
+        pc: usize,
+    },
+    /// An instruction pushed more than [`MAX_STACK_DEPTH`] values.
+    #[error("Stack overflow at instruction {pc}")]
+    StackOverflow {
+        /// Index of the failing instruction.
+        pc: usize,
+    },
+    /// An arithmetic instruction overflowed, divided by zero or used an invalid exponent.
+    #[error("{operation:?} failed at instruction {pc}")]
+    Arithmetic {
+        /// Index of the failing instruction.
+        pc: usize,
+        /// The operation that failed.
+        operation: Operation,
+    },
+    /// `Neg` was applied to `i128::MIN`.
+    #[error("Negation overflowed at instruction {pc}")]
+    NegationOverflow {
+        /// Index of the failing instruction.
+        pc: usize,
+    },
+    /// The result of the program does not fit into a register.
+    #[error("The result {result} does not fit into a register")]
+    ResultOutOfRange {
+        /// The value left on the stack.
+        result: i128,
+    },
+    /// The program did not leave exactly one value on the stack.
+    #[error("A program must leave exactly one value on the stack, got {depth}")]
+    UnbalancedStack {
+        /// The number of values left on the stack.
+        depth: usize,
+    },
+}
+
+impl Operation {
+    /// Signed counterpart of [`Operation::apply`] used by the stack machine.
+    fn apply_signed(self, lhs: i128, rhs: i128) -> Option<i128> {
+        match self {
+            Operation::Add => lhs.checked_add(rhs),
+            Operation::Sub => lhs.checked_sub(rhs),
+            Operation::Mul => lhs.checked_mul(rhs),
+            Operation::Div => lhs.checked_div(rhs),
+            Operation::Mod => lhs.checked_rem(rhs),
+            Operation::Pow => u32::try_from(rhs).ok().and_then(|exp| lhs.checked_pow(exp)),
+        }
+    }
+}
+
+/// Runs `program` and returns the only value left on the stack. `register` is the value
+/// pushed by [`Instruction::Load`].
+pub fn evaluate(program: &[Instruction], register: u64) -> Result<i128, VmError> {
+    if program.is_empty() || program.len() > MAX_PROGRAM_LEN {
+        return Err(VmError::InvalidProgramLength { len: program.len() });
+    }
+
+    let mut stack: Vec<i128> = Vec::with_capacity(MAX_STACK_DEPTH);
+
+    for (pc, instruction) in program.iter().enumerate() {
+        let underflow = VmError::StackUnderflow { pc };
+
+        match *instruction {
+            Instruction::Push(value) => stack.push(value.into()),
+            Instruction::Load => stack.push(register.into()),
+            Instruction::Apply(operation) => {
+                let rhs = stack.pop().ok_or(underflow.clone())?;
+                let lhs = stack.pop().ok_or(underflow)?;
+                let result = operation
+                    .apply_signed(lhs, rhs)
+                    .ok_or(VmError::Arithmetic { pc, operation })?;
+                stack.push(result);
+            }
+            Instruction::Neg => {
+                let value = stack.pop().ok_or(underflow)?;
+                stack.push(
+                    value
+                        .checked_neg()
+                        .ok_or(VmError::NegationOverflow { pc })?,
+                );
+            }
+            Instruction::Dup => {
+                let value = *stack.last().ok_or(underflow)?;
+                stack.push(value);
+            }
+            Instruction::Swap => {
+                let len = stack.len();
+                if len < 2 {
+                    return Err(underflow);
+                }
+                stack.swap(len - 1, len - 2);
+            }
+            Instruction::Drop => {
+                stack.pop().ok_or(underflow)?;
+            }
+        }
+
+        if stack.len() > MAX_STACK_DEPTH {
+            return Err(VmError::StackOverflow { pc });
+        }
+    }
+
+    match stack.as_slice() {
+        [result] => Ok(*result),
+        _ => Err(VmError::UnbalancedStack { depth: stack.len() }),
+    }
+}
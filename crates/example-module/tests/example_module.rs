@@ -1,31 +1,143 @@
-use example_module::{CallMessage, ExampleModule, ExampleModuleConfig, Response};
-use sov_modules_api::macros::UniversalWallet;
+use example_module::{
+    CallMessage, Event, ExampleModule, ExampleModuleConfig, FeeConfig, FeeRecipient, HistoryPage,
+    Instruction, Op, Operation, RegisterResponse, Response, VmError, MAX_BATCH_LEN,
+    MAX_STACK_DEPTH,
+};
+use sov_bank::{config_gas_token_id, get_token_id, Bank};
+use sov_modules_api::macros::{config_value, UniversalWallet};
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_modules_api::runtime::capabilities::TransactionAuthenticator;
 use sov_modules_api::sov_universal_wallet::schema::Schema;
+use sov_modules_api::transaction::{PriorityFeeBips, Transaction, UnsignedTransaction};
+use sov_modules_api::{GasArray, RawTx, Spec};
 use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
 use sov_test_utils::runtime::TestRunner;
-use sov_test_utils::{generate_optimistic_runtime, AsUser, TransactionTestCase};
+use sov_test_utils::{
+    generate_optimistic_runtime, AsUser, BatchTestCase, TestUser, TransactionTestCase,
+    TransactionType,
+};
 
 generate_optimistic_runtime!(ExampleModuleRuntime <= example_module: ExampleModule<S>);
 
 type S = sov_test_utils::TestSpec;
 
-#[test]
-fn test_example_module() {
-    // Generate a genesis config, then overwrite the attester key/address with ones that
-    // we know. We leave the other values untouched.
-    let genesis_config =
-        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(1);
+type Runner = TestRunner<ExampleModuleRuntime<S>, S>;
 
-    let user = genesis_config.additional_accounts.first().unwrap().clone();
+/// Runs the genesis for `accounts` new users, the first of which owns the module. `configure`
+/// fills in the rest of the module's genesis.
+fn setup(
+    accounts: usize,
+    configure: impl FnOnce(&mut ExampleModuleConfig<S>, &[TestUser<S>]),
+) -> (Vec<TestUser<S>>, Runner) {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(accounts);
+    let users = genesis_config.additional_accounts[..accounts].to_vec();
 
-    // Run genesis registering the attester and sequencer we've generated.
-    let genesis = GenesisConfig::from_minimal_config(genesis_config.into(), ExampleModuleConfig {});
+    let mut config = ExampleModuleConfig {
+        owner: users[0].address(),
+        writers: vec![],
+        initial_value: None,
+        fee: None,
+        registers: vec![],
+    };
+    configure(&mut config, &users);
 
-    let mut runner = TestRunner::new_with_genesis(
+    let genesis = GenesisConfig::from_minimal_config(genesis_config.into(), config);
+    let runner = TestRunner::new_with_genesis(
         genesis.into_genesis_params(),
         ExampleModuleRuntime::default(),
     );
 
+    (users, runner)
+}
+
+/// The maximum fee of the transactions built by [`evaluate_tx`].
+const MAX_FEE: u64 = 100_000_000;
+
+/// The first transaction of `user`, evaluating `program` with at most `gas_limit` gas in
+/// every dimension.
+fn evaluate_tx(
+    user: &TestUser<S>,
+    program: Vec<Instruction>,
+    gas_limit: u64,
+) -> TransactionType<ExampleModuleRuntime<S>, S> {
+    let call = ExampleModuleRuntimeCall::<S>::ExampleModule(CallMessage::Evaluate(program));
+    let tx = Transaction::<S>::new_signed_tx(
+        user.private_key(),
+        UnsignedTransaction::new(
+            borsh::to_vec(&call).unwrap(),
+            config_value!("CHAIN_ID"),
+            PriorityFeeBips::ZERO,
+            MAX_FEE,
+            0,
+            Some(<S as Spec>::Gas::from_slice(&[gas_limit; 2])),
+        ),
+    );
+    let input = ExampleModuleRuntime::<S>::add_standard_auth(RawTx {
+        data: borsh::to_vec(&tx).unwrap(),
+    });
+    TransactionType::PreSigned(RawTx {
+        data: borsh::to_vec(&input).unwrap(),
+    })
+}
+
+/// Evaluates each program as the first transaction of its user, all in one batch so that
+/// they pay the same gas price, and returns the fee paid by each user.
+fn evaluate_in_batch(
+    runner: &mut Runner,
+    programs: Vec<(&TestUser<S>, Vec<Instruction>)>,
+    gas_limit: u64,
+) -> Vec<u64> {
+    let balances: Vec<u64> = programs
+        .iter()
+        .map(|(user, _)| gas_balance(runner, &user.address()))
+        .collect();
+    let txs: Vec<_> = programs
+        .iter()
+        .map(|(user, program)| evaluate_tx(user, program.clone(), gas_limit))
+        .collect();
+    runner.execute_batch(BatchTestCase {
+        input: txs.into(),
+        assert: Box::new(|_result, _state| {}),
+    });
+
+    programs
+        .iter()
+        .zip(balances)
+        .map(|((user, _), balance)| balance - gas_balance(runner, &user.address()))
+        .collect()
+}
+
+/// The gas token balance of `address`.
+fn gas_balance(runner: &mut Runner, address: &<S as Spec>::Address) -> u64 {
+    runner.query_state(|state| {
+        Bank::<S>::default()
+            .get_balance_of(address, config_gas_token_id(), state)
+            .unwrap_infallible()
+            .unwrap_or_default()
+    })
+}
+
+/// Two programs with the same encoded size, so that they only differ in the gas charged for
+/// their instructions: the second one has 8 more.
+fn short_and_long_programs() -> (Vec<Instruction>, Vec<Instruction>) {
+    let short = vec![Instruction::Push(7)];
+    let mut long = vec![Instruction::Load];
+    for _ in 0..4 {
+        long.extend([Instruction::Dup, Instruction::Drop]);
+    }
+    assert_eq!(
+        borsh::to_vec(&short).unwrap().len(),
+        borsh::to_vec(&long).unwrap().len()
+    );
+    (short, long)
+}
+
+#[test]
+fn test_example_module() {
+    let (users, mut runner) = setup(1, |_, _| {});
+    let user = &users[0];
+
     runner.execute_transaction(TransactionTestCase {
         input: user.create_plain_message::<ExampleModule<S>>(CallMessage::SetValue(99)),
         assert: Box::new(|result, _state| {
@@ -34,7 +146,7 @@ fn test_example_module() {
             assert_eq!(result.events.len(), 1);
             assert_eq!(
                 result.events[0],
-                ExampleModuleRuntimeEvent::ExampleModule(example_module::Event::Set { value: 99 })
+                ExampleModuleRuntimeEvent::ExampleModule(Event::Set { value: 99 })
             );
         }),
     });
@@ -47,11 +159,683 @@ fn test_example_module() {
     });
 }
 
+#[test]
+fn test_genesis_initial_value() {
+    let (users, mut runner) = setup(2, |config, users| {
+        config.writers = vec![users[1].address()];
+        config.initial_value = Some(42);
+    });
+    let writer = &users[1];
+
+    runner.query_state(|state| {
+        let module = ExampleModule::<S>::default();
+        assert_eq!(module.query_value(state), Response { value: Some(42) });
+        assert_eq!(module.query_value_at_height(0, state).value, Some(42));
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: writer.create_plain_message::<ExampleModule<S>>(CallMessage::SetValue(43)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+}
+
+#[test]
+#[should_panic(expected = "is listed more than once in the genesis config")]
+fn test_genesis_rejects_duplicate_writers() {
+    setup(2, |config, users| {
+        config.writers = vec![users[1].address(), users[1].address()];
+    });
+}
+
+#[test]
+fn test_value_history() {
+    let (users, mut runner) = setup(1, |_, _| {});
+    let user = &users[0];
+
+    for value in [10, 20, 30] {
+        runner.execute_transaction(TransactionTestCase {
+            input: user.create_plain_message::<ExampleModule<S>>(CallMessage::SetValue(value)),
+            assert: Box::new(|result, _state| {
+                assert!(result.tx_receipt.is_successful());
+            }),
+        });
+    }
+
+    runner.query_state(|state| {
+        let module = ExampleModule::<S>::default();
+
+        let history = module.query_value_history(
+            &HistoryPage {
+                offset: 0,
+                limit: 10,
+            },
+            state,
+        );
+        assert_eq!(history.total, 3);
+        let values: Vec<u32> = history.entries.iter().map(|change| change.value).collect();
+        assert_eq!(values, vec![10, 20, 30]);
+        assert!(history.entries[0].height < history.entries[1].height);
+
+        let page = module.query_value_history(
+            &HistoryPage {
+                offset: 2,
+                limit: 10,
+            },
+            state,
+        );
+        assert_eq!(page.entries, history.entries[2..]);
+
+        let first_height = history.entries[0].height;
+        assert_eq!(
+            module.query_value_at_height(first_height - 1, state).value,
+            None
+        );
+        assert_eq!(
+            module.query_value_at_height(first_height, state).value,
+            Some(10)
+        );
+        assert_eq!(
+            module
+                .query_value_at_height(history.entries[1].height, state)
+                .value,
+            Some(20)
+        );
+        assert_eq!(
+            module.query_value_at_height(u64::MAX, state).value,
+            Some(30)
+        );
+    });
+}
+
+#[test]
+fn test_access_control() {
+    let (users, mut runner) = setup(3, |_, _| {});
+    let (owner, writer, outsider) = (&users[0], &users[1], &users[2]);
+
+    runner.execute_transaction(TransactionTestCase {
+        input: writer.create_plain_message::<ExampleModule<S>>(CallMessage::SetValue(1)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: outsider.create_plain_message::<ExampleModule<S>>(CallMessage::AddWriter {
+            writer: outsider.address(),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    let writer_address = writer.address();
+    runner.execute_transaction(TransactionTestCase {
+        input: owner.create_plain_message::<ExampleModule<S>>(CallMessage::AddWriter {
+            writer: writer.address(),
+        }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                result.events,
+                vec![ExampleModuleRuntimeEvent::ExampleModule(
+                    Event::WriterAdded {
+                        writer: writer_address
+                    }
+                )]
+            );
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: writer.create_plain_message::<ExampleModule<S>>(CallMessage::SetValue(2)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    let (owner_address, outsider_address) = (owner.address(), outsider.address());
+    runner.execute_transaction(TransactionTestCase {
+        input: owner.create_plain_message::<ExampleModule<S>>(CallMessage::TransferOwnership {
+            new_owner: outsider.address(),
+        }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                result.events,
+                vec![ExampleModuleRuntimeEvent::ExampleModule(
+                    Event::OwnershipTransferred {
+                        previous_owner: owner_address,
+                        new_owner: outsider_address,
+                    }
+                )]
+            );
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: owner.create_plain_message::<ExampleModule<S>>(CallMessage::SetValue(3)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: outsider.create_plain_message::<ExampleModule<S>>(CallMessage::RemoveWriter {
+            writer: writer.address(),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: writer.create_plain_message::<ExampleModule<S>>(CallMessage::SetValue(4)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    runner.query_state(|state| {
+        assert_eq!(
+            ExampleModule::<S>::default().query_value(state),
+            Response { value: Some(2) }
+        );
+    });
+}
+
+#[test]
+fn test_fees() {
+    const FEE: u64 = 500;
+
+    let (users, mut runner) = setup(3, |config, users| {
+        config.fee = Some(FeeConfig {
+            token_id: config_gas_token_id(),
+            amount: FEE,
+            recipient: FeeRecipient::Treasury(users[2].address()),
+        });
+    });
+    let (owner, user, treasury) = (&users[0], &users[1], &users[2]);
+    let initial_balance = treasury.available_gas_balance;
+
+    let user_address = user.address();
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::Add(1)),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                result.events[0],
+                ExampleModuleRuntimeEvent::ExampleModule(Event::FeeCharged {
+                    payer: user_address,
+                    token_id: config_gas_token_id(),
+                    amount: FEE,
+                })
+            );
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::SetFee { fee: None }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    let unknown_token_id = get_token_id::<S>("unknown", &owner.address());
+    let invalid_fees = [
+        FeeConfig {
+            token_id: config_gas_token_id(),
+            amount: 0,
+            recipient: FeeRecipient::Burn,
+        },
+        FeeConfig {
+            token_id: unknown_token_id,
+            amount: FEE,
+            recipient: FeeRecipient::Burn,
+        },
+    ];
+    for fee in invalid_fees {
+        runner.execute_transaction(TransactionTestCase {
+            input: owner
+                .create_plain_message::<ExampleModule<S>>(CallMessage::SetFee { fee: Some(fee) }),
+            assert: Box::new(|result, _state| {
+                assert!(result.tx_receipt.is_reverted());
+            }),
+        });
+    }
+
+    runner.execute_transaction(TransactionTestCase {
+        input: owner.create_plain_message::<ExampleModule<S>>(CallMessage::SetFee { fee: None }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::Add(1)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(result.events.len(), 1);
+        }),
+    });
+
+    let treasury_address = treasury.address();
+    runner.query_state(|state| {
+        let balance = Bank::<S>::default()
+            .get_balance_of(&treasury_address, config_gas_token_id(), state)
+            .unwrap_infallible();
+        assert_eq!(balance, Some(initial_balance + FEE));
+    });
+}
+
+#[test]
+fn test_burned_fees() {
+    const FEE: u64 = 300;
+    const TOKEN_NAME: &str = "fee-token";
+
+    let (users, mut runner) = setup(2, |_, _| {});
+    let (owner, user) = (&users[0], &users[1]);
+
+    // Gas fees are partly burned too, so the fee is paid in a token of its own.
+    let token_id = get_token_id::<S>(TOKEN_NAME, &owner.address());
+    runner.execute_transaction(TransactionTestCase {
+        input: owner.create_plain_message::<Bank<S>>(sov_bank::CallMessage::CreateToken {
+            token_name: TOKEN_NAME.to_string(),
+            initial_balance: 1_000,
+            mint_to_address: user.address(),
+            authorized_minters: vec![],
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+    runner.execute_transaction(TransactionTestCase {
+        input: owner.create_plain_message::<ExampleModule<S>>(CallMessage::SetFee {
+            fee: Some(FeeConfig {
+                token_id,
+                amount: FEE,
+                recipient: FeeRecipient::Burn,
+            }),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::Add(1)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    let user_address = user.address();
+    runner.query_state(|state| {
+        let bank = Bank::<S>::default();
+        assert_eq!(
+            bank.get_total_supply_of(&token_id, state)
+                .unwrap_infallible(),
+            Some(1_000 - FEE)
+        );
+        assert_eq!(
+            bank.get_balance_of(&user_address, token_id, state)
+                .unwrap_infallible(),
+            Some(1_000 - FEE)
+        );
+    });
+}
+
+#[test]
+fn test_calculator_registers() {
+    let (users, mut runner) = setup(2, |_, _| {});
+    let (user, other_user) = (&users[0], &users[1]);
+
+    for msg in [
+        CallMessage::Add(7),
+        CallMessage::Mul(6),
+        CallMessage::Sub(2),
+        CallMessage::Div(8),
+        CallMessage::Pow(3),
+        CallMessage::Mod(100),
+    ] {
+        runner.execute_transaction(TransactionTestCase {
+            input: user.create_plain_message::<ExampleModule<S>>(msg),
+            assert: Box::new(|result, _state| {
+                assert!(result.tx_receipt.is_successful());
+                assert_eq!(result.events.len(), 1);
+            }),
+        });
+    }
+
+    let user_address = user.address();
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::Add(1)),
+        assert: Box::new(move |result, _state| {
+            assert_eq!(
+                result.events[0],
+                ExampleModuleRuntimeEvent::ExampleModule(Event::Computed {
+                    account: user_address,
+                    operation: Operation::Add,
+                    operand: 1,
+                    result: 26,
+                })
+            );
+        }),
+    });
+
+    let user_address = user.address();
+    let other_address = other_user.address();
+    runner.query_state(|state| {
+        let module = ExampleModule::<S>::default();
+        assert_eq!(
+            module.query_register(&user_address, state),
+            RegisterResponse { value: 26 }
+        );
+        assert_eq!(
+            module.query_register(&other_address, state),
+            RegisterResponse { value: 0 }
+        );
+    });
+}
+
+#[test]
+fn test_calculator_errors_revert() {
+    let (users, mut runner) = setup(1, |_, _| {});
+    let user = &users[0];
+
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::Add(u64::MAX)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    for msg in [
+        CallMessage::Add(1),
+        CallMessage::Mul(2),
+        CallMessage::Pow(2),
+        CallMessage::Div(0),
+        CallMessage::Mod(0),
+    ] {
+        runner.execute_transaction(TransactionTestCase {
+            input: user.create_plain_message::<ExampleModule<S>>(msg),
+            assert: Box::new(|result, _state| {
+                assert!(result.tx_receipt.is_reverted());
+                assert!(result.events.is_empty());
+            }),
+        });
+    }
+
+    let user_address = user.address();
+    runner.query_state(|state| {
+        assert_eq!(
+            ExampleModule::<S>::default().query_register(&user_address, state),
+            RegisterResponse { value: u64::MAX }
+        );
+    });
+}
+
+#[test]
+fn test_batch() {
+    let (users, mut runner) = setup(1, |_, _| {});
+    let user = &users[0];
+
+    let op = |operation, operand| Op { operation, operand };
+
+    let user_address = user.address();
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::Batch(vec![
+            op(Operation::Add, 3),
+            op(Operation::Pow, 4),
+            op(Operation::Mod, 50),
+        ])),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                result.events,
+                vec![ExampleModuleRuntimeEvent::ExampleModule(
+                    Event::BatchComputed {
+                        account: user_address,
+                        operations: 3,
+                        initial: 0,
+                        result: 31,
+                    }
+                )]
+            );
+        }),
+    });
+
+    for batch in [
+        vec![op(Operation::Add, 1), op(Operation::Div, 0)],
+        vec![],
+        vec![op(Operation::Add, 1); MAX_BATCH_LEN + 1],
+    ] {
+        runner.execute_transaction(TransactionTestCase {
+            input: user.create_plain_message::<ExampleModule<S>>(CallMessage::Batch(batch)),
+            assert: Box::new(|result, _state| {
+                assert!(result.tx_receipt.is_reverted());
+            }),
+        });
+    }
+
+    let user_address = user.address();
+    runner.query_state(|state| {
+        assert_eq!(
+            ExampleModule::<S>::default().query_register(&user_address, state),
+            RegisterResponse { value: 31 }
+        );
+    });
+}
+
+#[test]
+fn test_evaluate_program() {
+    let (users, mut runner) = setup(1, |_, _| {});
+    let user = &users[0];
+
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::Add(5)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    // (register - 8) * -(register - 8) + 100 = 91
+    let program = vec![
+        Instruction::Load,
+        Instruction::Push(8),
+        Instruction::Apply(Operation::Sub),
+        Instruction::Dup,
+        Instruction::Neg,
+        Instruction::Apply(Operation::Mul),
+        Instruction::Push(100),
+        Instruction::Apply(Operation::Add),
+    ];
+
+    let user_address = user.address();
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::Evaluate(program)),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                result.events,
+                vec![ExampleModuleRuntimeEvent::ExampleModule(Event::Evaluated {
+                    account: user_address,
+                    instructions: 8,
+                    result: 91,
+                })]
+            );
+        }),
+    });
+
+    // Negative results do not fit into a register.
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::Evaluate(vec![
+            Instruction::Push(1),
+            Instruction::Neg,
+        ])),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    let user_address = user.address();
+    runner.query_state(|state| {
+        assert_eq!(
+            ExampleModule::<S>::default().query_register(&user_address, state),
+            RegisterResponse { value: 91 }
+        );
+    });
+}
+
+#[test]
+fn test_evaluate_gas_grows_with_program_length() {
+    let (users, mut runner) = setup(2, |_, _| {});
+    let (first, second) = (&users[0], &users[1]);
+
+    let (short, long) = short_and_long_programs();
+    let paid = evaluate_in_batch(&mut runner, vec![(first, short), (second, long)], 1_000_000);
+    assert!(paid[1] > paid[0], "{paid:?}");
+
+    let (first_address, second_address) = (first.address(), second.address());
+    runner.query_state(|state| {
+        let module = ExampleModule::<S>::default();
+        assert_eq!(
+            module.query_register(&first_address, state),
+            RegisterResponse { value: 7 }
+        );
+        assert_eq!(
+            module
+                .registers
+                .get(&second_address, state)
+                .unwrap_infallible(),
+            Some(0)
+        );
+    });
+}
+
+#[test]
+fn test_evaluate_runs_out_of_gas() {
+    let (users, mut runner) = setup(4, |_, _| {});
+
+    // Both gas dimensions are charged and priced the same, so the fees paid for the two
+    // programs give the gas used by the short one.
+    let [per_instruction, _]: [u64; 2] = config_value!("EXAMPLE_MODULE_GAS_PER_VM_INSTRUCTION");
+    let (short, long) = short_and_long_programs();
+    let paid = evaluate_in_batch(
+        &mut runner,
+        vec![(&users[0], short.clone()), (&users[1], long.clone())],
+        1_000_000,
+    );
+    let short_gas = paid[0] * 8 * per_instruction / (paid[1] - paid[0]);
+
+    // Enough for the short program and half of the extra instructions of the long one.
+    let gas_limit = short_gas + 4 * per_instruction;
+    let paid = evaluate_in_batch(
+        &mut runner,
+        vec![(&users[2], long), (&users[3], short)],
+        gas_limit,
+    );
+    assert!(paid[0] > 0);
+
+    let (out_of_gas, within_limit) = (users[2].address(), users[3].address());
+    runner.query_state(|state| {
+        let module = ExampleModule::<S>::default();
+        assert_eq!(
+            module.registers.get(&out_of_gas, state).unwrap_infallible(),
+            None
+        );
+        assert_eq!(
+            module
+                .registers
+                .get(&within_limit, state)
+                .unwrap_infallible(),
+            Some(7)
+        );
+    });
+}
+
+#[test]
+fn test_vm_errors() {
+    use example_module::evaluate;
+
+    assert_eq!(
+        evaluate(&[], 0),
+        Err(VmError::InvalidProgramLength { len: 0 })
+    );
+    assert_eq!(
+        evaluate(
+            &[Instruction::Push(1), Instruction::Apply(Operation::Add)],
+            0
+        ),
+        Err(VmError::StackUnderflow { pc: 1 })
+    );
+    assert_eq!(
+        evaluate(&vec![Instruction::Load; MAX_STACK_DEPTH + 1], 0),
+        Err(VmError::StackOverflow {
+            pc: MAX_STACK_DEPTH
+        })
+    );
+    assert_eq!(
+        evaluate(
+            &[
+                Instruction::Push(1),
+                Instruction::Push(0),
+                Instruction::Apply(Operation::Div)
+            ],
+            0
+        ),
+        Err(VmError::Arithmetic {
+            pc: 2,
+            operation: Operation::Div
+        })
+    );
+    assert_eq!(
+        evaluate(&[Instruction::Push(1), Instruction::Push(2)], 0),
+        Err(VmError::UnbalancedStack { depth: 2 })
+    );
+    assert_eq!(
+        evaluate(
+            &[
+                Instruction::Push(u64::MAX),
+                Instruction::Dup,
+                Instruction::Apply(Operation::Mul),
+                Instruction::Push(3),
+                Instruction::Apply(Operation::Sub),
+            ],
+            0
+        ),
+        Ok(i128::from(u64::MAX) * i128::from(u64::MAX) - 3)
+    );
+}
+
+#[test]
+fn test_operations() {
+    assert_eq!(
+        Operation::Sub.apply(3, 5),
+        Err(example_module::CalculatorError::Overflow {
+            operation: Operation::Sub,
+            lhs: 3,
+            rhs: 5,
+        })
+    );
+    assert_eq!(
+        Operation::Pow.apply(2, u64::from(u32::MAX) + 1),
+        Err(example_module::CalculatorError::Overflow {
+            operation: Operation::Pow,
+            lhs: 2,
+            rhs: u64::from(u32::MAX) + 1,
+        })
+    );
+    assert_eq!(Operation::Pow.apply(0, 0), Ok(1));
+    assert_eq!(Operation::Mod.apply(17, 5), Ok(2));
+}
+
 #[test]
 fn test_display_value_setter_call() {
     #[derive(Debug, Clone, PartialEq, borsh::BorshSerialize, UniversalWallet)]
     enum RuntimeCall {
-        ValueSetter(CallMessage),
+        ValueSetter(CallMessage<S>),
     }
 
     let msg = RuntimeCall::ValueSetter(CallMessage::SetValue(92));
@@ -0,0 +1,159 @@
+use std::fmt::Debug;
+
+use anyhow::Result;
+use sov_modules_api::macros::UniversalWallet;
+use sov_modules_api::prelude::*;
+#[cfg(feature = "native")]
+use sov_modules_api::schemars;
+use sov_modules_api::{CallResponse, Context, EventEmitter, Spec, TxState};
+
+use crate::error::GovernanceError;
+use crate::event::Event;
+use crate::{Governance, RawSequencerPolicy, UnregisteredCall, GOVERNANCE_MODULE};
+
+/// This enumeration represents the available call messages for interacting with
+/// the `Governance` module. All of them can only be sent by the governor.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(
+        bound = "S::Address: sov_modules_api::schemars::JsonSchema",
+        rename = "CallMessage"
+    )
+)]
+#[cfg_attr(
+    feature = "arbitrary",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Debug,
+    PartialEq,
+    UniversalWallet,
+    Clone,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CallMessage<S: Spec> {
+    /// Rejects every call to `module` until it is unpaused.
+    PauseModule {
+        /// The name of the module in the runtime, e.g. `bank`.
+        module: String,
+    },
+    /// Accepts calls to `module` again.
+    UnpauseModule {
+        /// The name of the module in the runtime, e.g. `bank`.
+        module: String,
+    },
+    /// Hands the governance role over to `new_governor`.
+    TransferGovernor {
+        /// The address of the new governor.
+        new_governor: S::Address,
+    },
+    /// Replaces the policy applied to the DA sender of every batch. DA addresses are given
+    /// as raw bytes.
+    SetSequencerPolicy {
+        /// The new policy.
+        policy: RawSequencerPolicy,
+    },
+    /// Replaces the calls that unregistered sequencers can submit.
+    SetUnregisteredCalls {
+        /// The allowed calls.
+        calls: Vec<UnregisteredCall>,
+    },
+}
+
+impl<S: Spec> Governance<S> {
+    /// Pauses `module`.
+    pub(crate) fn pause_module(
+        &self,
+        module: String,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        self.ensure_governor(context.sender(), state)?;
+
+        if module == GOVERNANCE_MODULE {
+            return Err(GovernanceError::<S::Address>::CannotPauseGovernance.into());
+        }
+
+        if !self.is_runtime_module(&module, state)? {
+            return Err(GovernanceError::<S::Address>::UnknownModule { module }.into());
+        }
+
+        if self.paused_modules.get(&module, state)?.is_some() {
+            return Err(GovernanceError::<S::Address>::AlreadyPaused { module }.into());
+        }
+
+        self.paused_modules.set(&module, &(), state)?;
+        self.emit_event(state, Event::ModulePaused { module });
+
+        Ok(CallResponse::default())
+    }
+
+    /// Unpauses `module`.
+    pub(crate) fn unpause_module(
+        &self,
+        module: String,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        self.ensure_governor(context.sender(), state)?;
+
+        if self.paused_modules.remove(&module, state)?.is_none() {
+            return Err(GovernanceError::<S::Address>::NotPaused { module }.into());
+        }
+
+        self.emit_event(state, Event::ModuleUnpaused { module });
+
+        Ok(CallResponse::default())
+    }
+
+    /// Transfers the governance role to `new_governor`.
+    pub(crate) fn transfer_governor(
+        &self,
+        new_governor: S::Address,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        let previous_governor = self.ensure_governor(context.sender(), state)?;
+
+        self.governor.set(&new_governor, state)?;
+        self.emit_event(
+            state,
+            Event::GovernorTransferred {
+                previous_governor,
+                new_governor,
+            },
+        );
+
+        Ok(CallResponse::default())
+    }
+
+    /// Returns `true` if the runtime has a module named `module`.
+    fn is_runtime_module(&self, module: &str, state: &mut impl TxState<S>) -> Result<bool> {
+        Ok(self
+            .runtime_modules
+            .get(state)?
+            .unwrap_or_default()
+            .iter()
+            .any(|name| name == module))
+    }
+
+    /// Returns the governor if it is `sender`, fails with [`GovernanceError::NotGovernor`] otherwise.
+    pub(crate) fn ensure_governor(
+        &self,
+        sender: &S::Address,
+        state: &mut impl TxState<S>,
+    ) -> Result<S::Address> {
+        match self.governor.get(state)? {
+            Some(governor) if &governor == sender => Ok(governor),
+            _ => Err(GovernanceError::NotGovernor {
+                sender: sender.clone(),
+            }
+            .into()),
+        }
+    }
+}
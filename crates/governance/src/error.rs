@@ -0,0 +1,33 @@
+use std::fmt::Display;
+
+/// Errors returned by the [`crate::Governance`] module.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GovernanceError<Address: Display> {
+    /// Only the governor can change the runtime policies.
+    #[error("{sender} is not the governor")]
+    NotGovernor {
+        /// The sender of the rejected call.
+        sender: Address,
+    },
+    /// The runtime has no module of this name.
+    #[error("The runtime has no module `{module}`")]
+    UnknownModule {
+        /// The name of the module.
+        module: String,
+    },
+    /// The governance module cannot be paused, it is needed to unpause the others.
+    #[error("The `governance` module cannot be paused")]
+    CannotPauseGovernance,
+    /// The module is already paused.
+    #[error("Module `{module}` is already paused")]
+    AlreadyPaused {
+        /// The name of the module.
+        module: String,
+    },
+    /// The module is not paused.
+    #[error("Module `{module}` is not paused")]
+    NotPaused {
+        /// The name of the module.
+        module: String,
+    },
+}
@@ -0,0 +1,39 @@
+use sov_modules_api::Spec;
+
+use crate::{RawSequencerPolicy, UnregisteredCall};
+
+/// Events emitted by the [`crate::Governance`] module.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Clone,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[serde(rename_all = "snake_case")]
+pub enum Event<S: Spec> {
+    /// Calls to `module` are now rejected.
+    ModulePaused { module: String },
+    /// Calls to `module` are accepted again.
+    ModuleUnpaused { module: String },
+    /// The governance role changed hands.
+    GovernorTransferred {
+        /// The governor before the transfer.
+        previous_governor: S::Address,
+        /// The governor after the transfer.
+        new_governor: S::Address,
+    },
+    /// The sequencer policy was replaced.
+    SequencerPolicyUpdated {
+        /// The new policy, with DA addresses as raw bytes.
+        policy: RawSequencerPolicy,
+    },
+    /// The calls unregistered sequencers can submit were replaced.
+    UnregisteredCallsUpdated {
+        /// The allowed calls.
+        calls: Vec<UnregisteredCall>,
+    },
+}
@@ -0,0 +1,52 @@
+use anyhow::{bail, Result};
+use sov_modules_api::{GenesisState, Module, Spec};
+
+use crate::unregistered_calls::validate_unregistered_calls;
+use crate::{Governance, GOVERNANCE_MODULE};
+
+impl<S: Spec> Governance<S> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as Module>::Config,
+        state: &mut impl GenesisState<S>,
+    ) -> Result<()> {
+        self.governor.set(&config.governor, state)?;
+        self.runtime_modules.set(&config.runtime_modules, state)?;
+        self.runtime_calls.set(&config.runtime_calls, state)?;
+
+        for module in &config.paused_modules {
+            if module == GOVERNANCE_MODULE {
+                bail!("Cannot pause module `{}`", GOVERNANCE_MODULE);
+            }
+            if !config.runtime_modules.contains(module) {
+                bail!(
+                    "Cannot pause module `{}`, the runtime has no such module",
+                    module
+                );
+            }
+            if self.paused_modules.get(module, state)?.is_some() {
+                bail!(
+                    "Module `{}` is listed more than once in the paused modules",
+                    module
+                );
+            }
+            self.paused_modules.set(module, &(), state)?;
+        }
+
+        let sequencer_policy = config
+            .sequencer_policy
+            .clone()
+            .map(|address| address.as_ref().to_vec());
+        self.sequencer_policy.set(&sequencer_policy, state)?;
+
+        validate_unregistered_calls(
+            &config.unregistered_calls,
+            &config.runtime_modules,
+            &config.runtime_calls,
+        )?;
+        self.unregistered_calls
+            .set(&config.unregistered_calls, state)?;
+
+        Ok(())
+    }
+}
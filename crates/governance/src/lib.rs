@@ -0,0 +1,153 @@
+mod call;
+mod error;
+mod event;
+mod genesis;
+mod sequencer_policy;
+mod unregistered_calls;
+pub use call::CallMessage;
+pub use error::GovernanceError;
+pub use event::Event;
+pub use sequencer_policy::{
+    RawSequencerPolicy, SequencerFilter, SequencerPolicy, SequencerPolicyError, SlotBatches,
+};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{
+    CallResponse, Context, DaSpec, Error, GenesisState, Module, ModuleId, ModuleInfo,
+    ModuleRestApi, Spec, StateMap, StateReader, StateValue, TxState,
+};
+use sov_state::namespaces::User;
+pub use unregistered_calls::{default_unregistered_calls, UnregisteredCall};
+
+/// The name of the [`Governance`] module in the runtime. It cannot be paused, and calls to it
+/// are never rejected as paused.
+pub const GOVERNANCE_MODULE: &str = "governance";
+
+/// Genesis configuration of the [`Governance`] module.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "S::Address: Serialize + serde::de::DeserializeOwned, \
+    <S::Da as DaSpec>::Address: Serialize + serde::de::DeserializeOwned")]
+#[serde(rename_all = "snake_case")]
+pub struct GovernanceConfig<S: Spec> {
+    /// The address allowed to change the runtime policies.
+    pub governor: S::Address,
+    /// Names of the runtime modules that start paused. Cannot include [`GOVERNANCE_MODULE`].
+    #[serde(default)]
+    pub paused_modules: Vec<String>,
+    /// Names of the modules of the runtime, the only ones that can be paused. Filled in by
+    /// the runtime rather than read from the genesis file.
+    #[serde(skip)]
+    pub runtime_modules: Vec<String>,
+    /// The named calls of the runtime, as `(module, call)` pairs. The only calls that can be
+    /// allowed by name to unregistered sequencers. Filled in by the runtime rather than read
+    /// from the genesis file.
+    #[serde(skip)]
+    pub runtime_calls: Vec<(String, String)>,
+    /// The policy applied to the DA sender of every batch. Every sender is accepted by default.
+    #[serde(default)]
+    pub sequencer_policy: SequencerPolicy<<S::Da as DaSpec>::Address>,
+    /// The calls unregistered sequencers can submit. Only registering as a sequencer by
+    /// default.
+    #[serde(default = "default_unregistered_calls")]
+    pub unregistered_calls: Vec<UnregisteredCall>,
+}
+
+/// Holds the runtime-wide policies that can be changed without a hard fork, and the
+/// governance address allowed to change them.
+///
+/// Modules are identified by the name of their field in the `Runtime` (e.g. `bank`). The
+/// module itself does not block anything: the runtime consults [`Governance::is_paused`] and
+/// [`Governance::is_unregistered_call_allowed`] when authenticating transactions, and
+/// [`Governance::check_sequencer`] before applying a batch.
+#[derive(Clone, ModuleInfo, ModuleRestApi)]
+pub struct Governance<S: Spec> {
+    /// Id of the module.
+    #[id]
+    pub id: ModuleId,
+
+    /// The address allowed to change the runtime policies.
+    #[state]
+    pub governor: StateValue<S::Address>,
+
+    /// Modules whose calls are rejected by the runtime.
+    #[state]
+    pub paused_modules: StateMap<String, ()>,
+
+    /// Names of the modules of the runtime, the only ones that can be paused.
+    #[state]
+    pub runtime_modules: StateValue<Vec<String>>,
+
+    /// The named calls of the runtime, as `(module, call)` pairs.
+    #[state]
+    pub runtime_calls: StateValue<Vec<(String, String)>>,
+
+    /// The policy applied to the DA sender of every batch. Every sender is accepted if unset.
+    #[state]
+    pub sequencer_policy: StateValue<RawSequencerPolicy>,
+
+    /// Batches accepted during the latest slot in which a rate-limited batch was submitted.
+    /// Replaced as a whole on the first batch of a later slot, so the counts of past slots
+    /// are never kept around.
+    #[state]
+    pub sequencer_batches: StateValue<SlotBatches>,
+
+    /// The calls unregistered sequencers can submit. [`default_unregistered_calls`] if unset.
+    #[state]
+    pub unregistered_calls: StateValue<Vec<UnregisteredCall>>,
+}
+
+impl<S: Spec> Governance<S> {
+    /// Returns `true` if calls to `module` are currently paused.
+    pub fn is_paused<Reader: StateReader<User>>(
+        &self,
+        module: &str,
+        state: &mut Reader,
+    ) -> Result<bool, Reader::Error> {
+        Ok(self
+            .paused_modules
+            .get(&module.to_owned(), state)?
+            .is_some())
+    }
+}
+
+impl<S: Spec> Module for Governance<S> {
+    type Spec = S;
+
+    type Config = GovernanceConfig<S>;
+
+    type CallMessage = call::CallMessage<S>;
+
+    type Event = Event<S>;
+
+    fn genesis(
+        &self,
+        config: &Self::Config,
+        state: &mut impl GenesisState<S>,
+    ) -> Result<(), Error> {
+        Ok(self.init_module(config, state)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Context<Self::Spec>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::PauseModule { module } => {
+                Ok(self.pause_module(module, context, state)?)
+            }
+            call::CallMessage::UnpauseModule { module } => {
+                Ok(self.unpause_module(module, context, state)?)
+            }
+            call::CallMessage::TransferGovernor { new_governor } => {
+                Ok(self.transfer_governor(new_governor, context, state)?)
+            }
+            call::CallMessage::SetSequencerPolicy { policy } => {
+                Ok(self.set_sequencer_policy(policy, context, state)?)
+            }
+            call::CallMessage::SetUnregisteredCalls { calls } => {
+                Ok(self.set_unregistered_calls(calls, context, state)?)
+            }
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! Runtime policy deciding which DA addresses may submit batches.
+//!
+//! DA addresses are stored as raw bytes, like in the sequencer registry, so that the policy
+//! can be updated through call messages regardless of the DA layer.
+use std::fmt::Display;
+
+use anyhow::{Context as _, Result};
+use sov_modules_api::macros::UniversalWallet;
+use sov_modules_api::prelude::*;
+#[cfg(feature = "native")]
+use sov_modules_api::schemars;
+use sov_modules_api::{
+    CallResponse, Context, DaSpec, EventEmitter, Spec, StateReaderAndWriter, TxState,
+};
+use sov_state::namespaces::User;
+
+use crate::event::Event;
+use crate::Governance;
+
+/// Which DA addresses may submit batches.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "arbitrary",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    UniversalWallet,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SequencerFilter<Address> {
+    /// Every sender is accepted.
+    Open,
+    /// Only the listed senders are accepted.
+    Allowlist(Vec<Address>),
+    /// The listed senders are rejected.
+    Denylist(Vec<Address>),
+}
+
+/// The policy applied to the DA sender of every batch, before any of its transactions is
+/// processed.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "arbitrary",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    UniversalWallet,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct SequencerPolicy<Address> {
+    /// Which senders are accepted.
+    #[serde(default)]
+    pub filter: SequencerFilter<Address>,
+    /// Maximum number of batches a single sender can submit per slot. Unlimited if `None`.
+    #[serde(default)]
+    pub max_batches_per_slot: Option<u64>,
+}
+
+// Implemented by hand: deriving `Default` would require `Address: Default`.
+impl<Address> Default for SequencerFilter<Address> {
+    fn default() -> Self {
+        Self::Open
+    }
+}
+
+impl<Address> Default for SequencerPolicy<Address> {
+    fn default() -> Self {
+        Self {
+            filter: SequencerFilter::Open,
+            max_batches_per_slot: None,
+        }
+    }
+}
+
+/// A [`SequencerPolicy`] with DA addresses stored as raw bytes.
+pub type RawSequencerPolicy = SequencerPolicy<Vec<u8>>;
+
+impl<Address> SequencerPolicy<Address> {
+    /// Converts the addresses of the policy with `f`.
+    pub fn map<T>(self, mut f: impl FnMut(Address) -> T) -> SequencerPolicy<T> {
+        match self.try_map(|address| Ok::<_, std::convert::Infallible>(f(address))) {
+            Ok(policy) => policy,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Converts the addresses of the policy with `f`, stopping at the first error.
+    pub fn try_map<T, E>(
+        self,
+        mut f: impl FnMut(Address) -> Result<T, E>,
+    ) -> Result<SequencerPolicy<T>, E> {
+        let filter = match self.filter {
+            SequencerFilter::Open => SequencerFilter::Open,
+            SequencerFilter::Allowlist(addresses) => SequencerFilter::Allowlist(
+                addresses
+                    .into_iter()
+                    .map(&mut f)
+                    .collect::<Result<_, _>>()?,
+            ),
+            SequencerFilter::Denylist(addresses) => SequencerFilter::Denylist(
+                addresses
+                    .into_iter()
+                    .map(&mut f)
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+
+        Ok(SequencerPolicy {
+            filter,
+            max_batches_per_slot: self.max_batches_per_slot,
+        })
+    }
+}
+
+/// Number of batches accepted from each sender during a slot.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+pub struct SlotBatches {
+    /// The rollup height of the slot.
+    pub slot: u64,
+    /// Number of batches accepted from each sender during `slot`, by raw DA address.
+    pub senders: Vec<(Vec<u8>, u64)>,
+}
+
+/// Reasons for rejecting a batch.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SequencerPolicyError<DaAddress: Display> {
+    /// The sender is not in the allowlist.
+    #[error("Sequencer {sender} is not in the allowlist")]
+    NotAllowed {
+        /// The DA sender of the batch.
+        sender: DaAddress,
+    },
+    /// The sender is in the denylist.
+    #[error("Sequencer {sender} is in the denylist")]
+    Denied {
+        /// The DA sender of the batch.
+        sender: DaAddress,
+    },
+    /// The sender already submitted the maximum number of batches for this slot.
+    #[error("Sequencer {sender} already submitted {max} batches in this slot")]
+    RateLimited {
+        /// The DA sender of the batch.
+        sender: DaAddress,
+        /// The configured maximum.
+        max: u64,
+    },
+}
+
+impl<S: Spec> Governance<S> {
+    /// Checks that `sender` may submit a batch in the slot at rollup height `slot` and, if it
+    /// may, counts the batch against its rate limit.
+    pub fn check_sequencer(
+        &self,
+        sender: &<S::Da as DaSpec>::Address,
+        slot: u64,
+        state: &mut impl StateReaderAndWriter<User>,
+    ) -> Result<()> {
+        let Some(policy) = self.sequencer_policy.get(state).unwrap_infallible() else {
+            return Ok(());
+        };
+        let raw_sender = sender.as_ref().to_vec();
+
+        match &policy.filter {
+            SequencerFilter::Open => {}
+            SequencerFilter::Allowlist(allowed) if !allowed.contains(&raw_sender) => {
+                return Err(SequencerPolicyError::NotAllowed {
+                    sender: sender.clone(),
+                }
+                .into());
+            }
+            SequencerFilter::Denylist(denied) if denied.contains(&raw_sender) => {
+                return Err(SequencerPolicyError::Denied {
+                    sender: sender.clone(),
+                }
+                .into());
+            }
+            SequencerFilter::Allowlist(_) | SequencerFilter::Denylist(_) => {}
+        }
+
+        if let Some(max) = policy.max_batches_per_slot {
+            let mut batches = match self.sequencer_batches.get(state).unwrap_infallible() {
+                Some(batches) if batches.slot == slot => batches,
+                _ => SlotBatches {
+                    slot,
+                    senders: vec![],
+                },
+            };
+
+            let position = batches
+                .senders
+                .iter()
+                .position(|(address, _)| *address == raw_sender);
+            let count = position.map_or(0, |i| batches.senders[i].1);
+
+            if count >= max {
+                return Err(SequencerPolicyError::RateLimited {
+                    sender: sender.clone(),
+                    max,
+                }
+                .into());
+            }
+
+            match position {
+                Some(i) => batches.senders[i].1 += 1,
+                None => batches.senders.push((raw_sender, 1)),
+            }
+
+            self.sequencer_batches
+                .set(&batches, state)
+                .unwrap_infallible();
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the sequencer policy.
+    pub(crate) fn set_sequencer_policy(
+        &self,
+        policy: RawSequencerPolicy,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        self.ensure_governor(context.sender(), state)?;
+
+        // Make sure every address is valid on the DA layer, a typo would otherwise lock out
+        // every sequencer of an allowlist.
+        let policy = policy.try_map(|address| {
+            <S::Da as DaSpec>::Address::try_from(address.as_slice())
+                .map(|_| address)
+                .context("Invalid DA address in the sequencer policy")
+        })?;
+
+        self.sequencer_policy.set(&policy, state)?;
+        self.emit_event(state, Event::SequencerPolicyUpdated { policy });
+
+        Ok(CallResponse::default())
+    }
+}
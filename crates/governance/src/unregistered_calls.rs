@@ -0,0 +1,136 @@
+//! Runtime policy deciding which calls can be sent by unregistered sequencers.
+//!
+//! Transactions submitted by a DA address that is not a registered sequencer bypass the
+//! usual sequencer checks, so the runtime only accepts the calls listed here from them.
+use anyhow::{bail, Result};
+use sov_modules_api::macros::UniversalWallet;
+use sov_modules_api::prelude::*;
+#[cfg(feature = "native")]
+use sov_modules_api::schemars;
+use sov_modules_api::{CallResponse, Context, EventEmitter, Spec, StateReader, TxState};
+use sov_state::namespaces::User;
+
+use crate::event::Event;
+use crate::Governance;
+
+/// A call that unregistered sequencers are allowed to submit.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "arbitrary",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    UniversalWallet,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct UnregisteredCall {
+    /// The name of the module in the runtime, e.g. `sequencer_registry`.
+    pub module: String,
+    /// The name of the call message variant, as in its JSON representation (e.g. `register`).
+    /// Every call of the module is allowed if `None`.
+    #[serde(default)]
+    pub call: Option<String>,
+}
+
+/// The calls unregistered sequencers can submit when nothing else is configured: registering
+/// as a sequencer.
+pub fn default_unregistered_calls() -> Vec<UnregisteredCall> {
+    vec![UnregisteredCall {
+        module: "sequencer_registry".to_string(),
+        call: Some("register".to_string()),
+    }]
+}
+
+/// Fails if `calls` contains an empty module name, a module that is not in `runtime_modules`,
+/// a call that is not in `runtime_calls`, or the same entry twice.
+pub(crate) fn validate_unregistered_calls(
+    calls: &[UnregisteredCall],
+    runtime_modules: &[String],
+    runtime_calls: &[(String, String)],
+) -> Result<()> {
+    for (index, entry) in calls.iter().enumerate() {
+        if entry.module.is_empty() {
+            bail!("The unregistered calls must name a module");
+        }
+        if !runtime_modules.contains(&entry.module) {
+            bail!(
+                "Cannot allow calls to module `{}`, the runtime has no such module",
+                entry.module
+            );
+        }
+        if let Some(call) = &entry.call {
+            if !runtime_calls
+                .iter()
+                .any(|(module, name)| module == &entry.module && name == call)
+            {
+                bail!(
+                    "Cannot allow call `{}` of module `{}`, the module has no such call",
+                    call,
+                    entry.module
+                );
+            }
+        }
+        if calls[..index].contains(entry) {
+            bail!(
+                "Call {:?} of module `{}` is listed more than once in the unregistered calls",
+                entry.call,
+                entry.module
+            );
+        }
+    }
+
+    Ok(())
+}
+
+impl<S: Spec> Governance<S> {
+    /// Returns `true` if unregistered sequencers can submit the call `call` of `module`.
+    /// `call` is `None` for calls that have no name, which are only allowed if every call of
+    /// the module is.
+    pub fn is_unregistered_call_allowed<Reader: StateReader<User>>(
+        &self,
+        module: &str,
+        call: Option<&str>,
+        state: &mut Reader,
+    ) -> Result<bool, Reader::Error> {
+        let allowed = self
+            .unregistered_calls
+            .get(state)?
+            .unwrap_or_else(default_unregistered_calls);
+
+        Ok(allowed.iter().any(|entry| {
+            entry.module == module
+                && match &entry.call {
+                    None => true,
+                    Some(name) => call == Some(name.as_str()),
+                }
+        }))
+    }
+
+    /// Replaces the calls unregistered sequencers can submit.
+    pub(crate) fn set_unregistered_calls(
+        &self,
+        calls: Vec<UnregisteredCall>,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        self.ensure_governor(context.sender(), state)?;
+        validate_unregistered_calls(
+            &calls,
+            &self.runtime_modules.get(state)?.unwrap_or_default(),
+            &self.runtime_calls.get(state)?.unwrap_or_default(),
+        )?;
+
+        self.unregistered_calls.set(&calls, state)?;
+        self.emit_event(state, Event::UnregisteredCallsUpdated { calls });
+
+        Ok(CallResponse::default())
+    }
+}
@@ -0,0 +1,405 @@
+use governance::{
+    default_unregistered_calls, CallMessage, Event, Governance, GovernanceConfig, SequencerFilter,
+    SequencerPolicy, SlotBatches, UnregisteredCall,
+};
+use sov_mock_da::MockAddress;
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::runtime::TestRunner;
+use sov_test_utils::{generate_optimistic_runtime, AsUser, TestUser, TransactionTestCase};
+
+generate_optimistic_runtime!(GovernanceRuntime <= governance: Governance<S>);
+
+type S = sov_test_utils::TestSpec;
+
+/// The module names the governance module is configured with in these tests.
+fn runtime_modules() -> Vec<String> {
+    ["bank", "example_module", "governance", "sequencer_registry"]
+        .map(String::from)
+        .to_vec()
+}
+
+/// The call names the governance module is configured with in these tests.
+fn runtime_calls() -> Vec<(String, String)> {
+    [
+        ("bank", "transfer"),
+        ("bank", "burn"),
+        ("sequencer_registry", "register"),
+        ("sequencer_registry", "exit"),
+    ]
+    .map(|(module, call)| (module.to_string(), call.to_string()))
+    .to_vec()
+}
+
+#[test]
+fn test_pause_and_unpause() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(2);
+
+    let governor = genesis_config.additional_accounts[0].clone();
+    let user = genesis_config.additional_accounts[1].clone();
+
+    let genesis = GenesisConfig::from_minimal_config(
+        genesis_config.into(),
+        GovernanceConfig {
+            governor: governor.address(),
+            paused_modules: vec!["example_module".to_string()],
+            runtime_modules: runtime_modules(),
+            runtime_calls: runtime_calls(),
+            sequencer_policy: SequencerPolicy::default(),
+            unregistered_calls: default_unregistered_calls(),
+        },
+    );
+
+    let mut runner =
+        TestRunner::new_with_genesis(genesis.into_genesis_params(), GovernanceRuntime::default());
+
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<Governance<S>>(CallMessage::PauseModule {
+            module: "bank".to_string(),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(CallMessage::PauseModule {
+            module: "unknown".to_string(),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    // The governance module is needed to unpause the others.
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(CallMessage::PauseModule {
+            module: "governance".to_string(),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(CallMessage::PauseModule {
+            module: "bank".to_string(),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                result.events,
+                vec![GovernanceRuntimeEvent::Governance(Event::ModulePaused {
+                    module: "bank".to_string()
+                })]
+            );
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(CallMessage::UnpauseModule {
+            module: "example_module".to_string(),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                result.events,
+                vec![GovernanceRuntimeEvent::Governance(Event::ModuleUnpaused {
+                    module: "example_module".to_string()
+                })]
+            );
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(CallMessage::UnpauseModule {
+            module: "example_module".to_string(),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    runner.query_state(|state| {
+        let governance = Governance::<S>::default();
+        assert!(governance.is_paused("bank", state).unwrap_infallible());
+        assert!(!governance
+            .is_paused("governance", state)
+            .unwrap_infallible());
+        assert!(!governance
+            .is_paused("example_module", state)
+            .unwrap_infallible());
+    });
+}
+
+#[test]
+fn test_transfer_governor() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(2);
+
+    let governor = genesis_config.additional_accounts[0].clone();
+    let successor = genesis_config.additional_accounts[1].clone();
+
+    let genesis = GenesisConfig::from_minimal_config(
+        genesis_config.into(),
+        GovernanceConfig {
+            governor: governor.address(),
+            paused_modules: vec![],
+            runtime_modules: runtime_modules(),
+            runtime_calls: runtime_calls(),
+            sequencer_policy: SequencerPolicy::default(),
+            unregistered_calls: default_unregistered_calls(),
+        },
+    );
+
+    let mut runner =
+        TestRunner::new_with_genesis(genesis.into_genesis_params(), GovernanceRuntime::default());
+
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(CallMessage::TransferGovernor {
+            new_governor: successor.address(),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(CallMessage::PauseModule {
+            module: "bank".to_string(),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: successor.create_plain_message::<Governance<S>>(CallMessage::PauseModule {
+            module: "bank".to_string(),
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+}
+
+fn setup_with_policy(
+    policy: SequencerPolicy<MockAddress>,
+) -> (TestUser<S>, TestRunner<GovernanceRuntime<S>, S>) {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(1);
+
+    let governor = genesis_config.additional_accounts[0].clone();
+
+    let genesis = GenesisConfig::from_minimal_config(
+        genesis_config.into(),
+        GovernanceConfig {
+            governor: governor.address(),
+            paused_modules: vec![],
+            runtime_modules: runtime_modules(),
+            runtime_calls: runtime_calls(),
+            sequencer_policy: policy,
+            unregistered_calls: default_unregistered_calls(),
+        },
+    );
+
+    let runner =
+        TestRunner::new_with_genesis(genesis.into_genesis_params(), GovernanceRuntime::default());
+
+    (governor, runner)
+}
+
+#[test]
+fn test_sequencer_allowlist() {
+    let allowed = MockAddress::new([1; 32]);
+    let other = MockAddress::new([2; 32]);
+
+    let (_, mut runner) = setup_with_policy(SequencerPolicy {
+        filter: SequencerFilter::Allowlist(vec![allowed]),
+        max_batches_per_slot: None,
+    });
+
+    runner.query_state(|state| {
+        let governance = Governance::<S>::default();
+        assert!(governance.check_sequencer(&allowed, 1, state).is_ok());
+        assert!(governance.check_sequencer(&allowed, 1, state).is_ok());
+        assert!(governance.check_sequencer(&other, 1, state).is_err());
+    });
+}
+
+#[test]
+fn test_sequencer_rate_limit() {
+    let sequencer = MockAddress::new([1; 32]);
+    let other = MockAddress::new([2; 32]);
+
+    let (_, mut runner) = setup_with_policy(SequencerPolicy {
+        filter: SequencerFilter::Open,
+        max_batches_per_slot: Some(2),
+    });
+
+    runner.query_state(|state| {
+        let governance = Governance::<S>::default();
+        assert!(governance.check_sequencer(&sequencer, 1, state).is_ok());
+        assert!(governance.check_sequencer(&sequencer, 1, state).is_ok());
+        assert!(governance.check_sequencer(&sequencer, 1, state).is_err());
+        // The limit is per sender.
+        assert!(governance.check_sequencer(&other, 1, state).is_ok());
+
+        // The counts of a slot are dropped on the first batch of a later slot.
+        assert!(governance.check_sequencer(&other, 2, state).is_ok());
+        assert_eq!(
+            governance.sequencer_batches.get(state).unwrap_infallible(),
+            Some(SlotBatches {
+                slot: 2,
+                senders: vec![(other.as_ref().to_vec(), 1)],
+            })
+        );
+        assert!(governance.check_sequencer(&sequencer, 2, state).is_ok());
+        assert!(governance.check_sequencer(&sequencer, 2, state).is_ok());
+        assert!(governance.check_sequencer(&sequencer, 2, state).is_err());
+    });
+}
+
+#[test]
+fn test_set_sequencer_policy() {
+    let denied = MockAddress::new([3; 32]);
+
+    let (governor, mut runner) = setup_with_policy(SequencerPolicy::default());
+
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(CallMessage::SetSequencerPolicy {
+            policy: SequencerPolicy {
+                filter: SequencerFilter::Denylist(vec![vec![3; 4]]),
+                max_batches_per_slot: None,
+            },
+        }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    let policy = SequencerPolicy {
+        filter: SequencerFilter::Denylist(vec![denied.as_ref().to_vec()]),
+        max_batches_per_slot: None,
+    };
+
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(CallMessage::SetSequencerPolicy {
+            policy: policy.clone(),
+        }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                result.events,
+                vec![GovernanceRuntimeEvent::Governance(
+                    Event::SequencerPolicyUpdated { policy }
+                )]
+            );
+        }),
+    });
+
+    runner.query_state(|state| {
+        let governance = Governance::<S>::default();
+        assert!(governance.check_sequencer(&denied, 1, state).is_err());
+        assert!(governance
+            .check_sequencer(&MockAddress::new([4; 32]), 1, state)
+            .is_ok());
+    });
+}
+
+#[test]
+fn test_unregistered_calls() {
+    let (governor, mut runner) = setup_with_policy(SequencerPolicy::default());
+
+    // Only sequencer registrations are allowed by default.
+    runner.query_state(|state| {
+        let governance = Governance::<S>::default();
+        assert!(governance
+            .is_unregistered_call_allowed("sequencer_registry", Some("register"), state)
+            .unwrap_infallible());
+        assert!(!governance
+            .is_unregistered_call_allowed("sequencer_registry", Some("exit"), state)
+            .unwrap_infallible());
+        assert!(!governance
+            .is_unregistered_call_allowed("bank", Some("transfer"), state)
+            .unwrap_infallible());
+    });
+
+    let calls = vec![
+        UnregisteredCall {
+            module: "bank".to_string(),
+            call: Some("transfer".to_string()),
+        },
+        UnregisteredCall {
+            module: "governance".to_string(),
+            call: None,
+        },
+    ];
+
+    // Entries must name a module and a call of the runtime, and be unique.
+    for invalid in [
+        vec![UnregisteredCall {
+            module: String::new(),
+            call: None,
+        }],
+        vec![UnregisteredCall {
+            module: "unknown".to_string(),
+            call: None,
+        }],
+        vec![UnregisteredCall {
+            module: "bank".to_string(),
+            call: Some("unknown".to_string()),
+        }],
+        vec![UnregisteredCall {
+            module: "bank".to_string(),
+            call: Some("register".to_string()),
+        }],
+        vec![calls[0].clone(), calls[0].clone()],
+    ] {
+        runner.execute_transaction(TransactionTestCase {
+            input: governor.create_plain_message::<Governance<S>>(
+                CallMessage::SetUnregisteredCalls { calls: invalid },
+            ),
+            assert: Box::new(|result, _state| {
+                assert!(result.tx_receipt.is_reverted());
+            }),
+        });
+    }
+
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(CallMessage::SetUnregisteredCalls {
+            calls: calls.clone(),
+        }),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                result.events,
+                vec![GovernanceRuntimeEvent::Governance(
+                    Event::UnregisteredCallsUpdated { calls }
+                )]
+            );
+        }),
+    });
+
+    runner.query_state(|state| {
+        let governance = Governance::<S>::default();
+        assert!(governance
+            .is_unregistered_call_allowed("bank", Some("transfer"), state)
+            .unwrap_infallible());
+        assert!(!governance
+            .is_unregistered_call_allowed("bank", Some("burn"), state)
+            .unwrap_infallible());
+        // Every call of the module is allowed when no call is named.
+        assert!(governance
+            .is_unregistered_call_allowed("governance", Some("unpause_module"), state)
+            .unwrap_infallible());
+        assert!(governance
+            .is_unregistered_call_allowed("governance", None, state)
+            .unwrap_infallible());
+        // Registrations are no longer allowed.
+        assert!(!governance
+            .is_unregistered_call_allowed("sequencer_registry", Some("register"), state)
+            .unwrap_infallible());
+    });
+}
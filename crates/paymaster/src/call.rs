@@ -0,0 +1,87 @@
+use std::fmt::Debug;
+
+use anyhow::Result;
+use sov_modules_api::macros::UniversalWallet;
+use sov_modules_api::prelude::*;
+#[cfg(feature = "native")]
+use sov_modules_api::schemars;
+use sov_modules_api::{CallResponse, Context, EventEmitter, Spec, TxState};
+
+use crate::error::PaymasterError;
+use crate::event::Event;
+use crate::{Paymaster, SponsorPolicy};
+
+/// This enumeration represents the available call messages for interacting with
+/// the `Paymaster` module.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(
+        bound = "S::Address: sov_modules_api::schemars::JsonSchema",
+        rename = "CallMessage"
+    )
+)]
+#[cfg_attr(
+    feature = "arbitrary",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Debug,
+    PartialEq,
+    UniversalWallet,
+    Clone,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CallMessage<S: Spec> {
+    /// Registers or replaces the sponsorship policy of the sender.
+    SetPolicy {
+        /// Names of the runtime modules whose calls are sponsored.
+        modules: Vec<String>,
+        /// The users whose transactions are sponsored.
+        users: Vec<S::Address>,
+        /// Sponsors every user instead of `users`, which must then be empty.
+        allow_all_users: bool,
+        /// The gas fees the sponsor agrees to pay, replacing the remaining budget.
+        budget: u64,
+    },
+    /// Stops sponsoring transactions.
+    RemovePolicy,
+}
+
+impl<S: Spec> Paymaster<S> {
+    /// Registers `policy` for the sender.
+    pub(crate) fn set_policy(
+        &self,
+        policy: SponsorPolicy<S>,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        policy.validate()?;
+
+        let sponsor = context.sender().clone();
+        self.policies.set(&sponsor, &policy, state)?;
+        self.emit_event(state, Event::PolicySet { sponsor, policy });
+
+        Ok(CallResponse::default())
+    }
+
+    /// Removes the policy of the sender.
+    pub(crate) fn remove_policy(
+        &self,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        let sponsor = context.sender().clone();
+
+        if self.policies.remove(&sponsor, state)?.is_none() {
+            return Err(PaymasterError::NotASponsor { sponsor }.into());
+        }
+        self.emit_event(state, Event::PolicyRemoved { sponsor });
+
+        Ok(CallResponse::default())
+    }
+}
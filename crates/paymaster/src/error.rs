@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+/// Errors returned by the [`crate::Paymaster`] module.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PaymasterError<Address: Display> {
+    /// A policy must sponsor at least one module.
+    #[error("A sponsorship policy must cover at least one module")]
+    NoModules,
+    /// A policy must list the sponsored users, or sponsor everyone explicitly.
+    #[error("A sponsorship policy must list its users or set `allow_all_users`")]
+    NoUsers,
+    /// A policy sponsoring everyone cannot list users as well.
+    #[error("A sponsorship policy with `allow_all_users` cannot list users")]
+    UsersWithAllowAll,
+    /// The account has no sponsorship policy.
+    #[error("{sponsor} does not sponsor any transaction")]
+    NotASponsor {
+        /// The account named as sponsor.
+        sponsor: Address,
+    },
+    /// The policy of the sponsor does not cover the called module.
+    #[error("{sponsor} does not sponsor calls to module `{module}`")]
+    ModuleNotSponsored {
+        /// The account named as sponsor.
+        sponsor: Address,
+        /// The module called by the transaction.
+        module: String,
+    },
+    /// The policy of the sponsor does not cover the sender.
+    #[error("{sponsor} does not sponsor transactions from {user}")]
+    UserNotSponsored {
+        /// The account named as sponsor.
+        sponsor: Address,
+        /// The sender of the transaction.
+        user: Address,
+    },
+    /// The maximum fee of the transaction exceeds the remaining budget of the sponsor.
+    #[error("{sponsor} has {budget} left to sponsor, the transaction may cost {max_fee}")]
+    BudgetExceeded {
+        /// The account named as sponsor.
+        sponsor: Address,
+        /// The remaining budget of the sponsor.
+        budget: u64,
+        /// The maximum fee of the transaction.
+        max_fee: u64,
+    },
+}
@@ -0,0 +1,30 @@
+use sov_modules_api::Spec;
+
+use crate::SponsorPolicy;
+
+/// Events emitted by the [`crate::Paymaster`] module.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Clone,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[serde(rename_all = "snake_case")]
+pub enum Event<S: Spec> {
+    /// `sponsor` registered or replaced its policy.
+    PolicySet {
+        /// The account paying the fees.
+        sponsor: S::Address,
+        /// The transactions it pays the fees of.
+        policy: SponsorPolicy<S>,
+    },
+    /// `sponsor` stopped sponsoring transactions.
+    PolicyRemoved {
+        /// The former sponsor.
+        sponsor: S::Address,
+    },
+}
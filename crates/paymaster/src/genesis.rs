@@ -0,0 +1,28 @@
+use anyhow::{bail, Result};
+use sov_modules_api::{GenesisState, Module, Spec};
+
+use crate::Paymaster;
+
+impl<S: Spec> Paymaster<S> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as Module>::Config,
+        state: &mut impl GenesisState<S>,
+    ) -> Result<()> {
+        for sponsor in &config.sponsors {
+            if self.policies.get(&sponsor.sponsor, state)?.is_some() {
+                bail!(
+                    "Sponsor {} is listed more than once in the paymaster genesis",
+                    sponsor.sponsor
+                );
+            }
+            if let Err(e) = sponsor.policy.validate() {
+                bail!("Invalid policy for sponsor {}: {e}", sponsor.sponsor);
+            }
+            self.policies
+                .set(&sponsor.sponsor, &sponsor.policy, state)?;
+        }
+
+        Ok(())
+    }
+}
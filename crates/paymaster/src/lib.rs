@@ -0,0 +1,140 @@
+mod call;
+mod error;
+mod event;
+mod genesis;
+mod sponsorship;
+pub use call::CallMessage;
+pub use error::PaymasterError;
+pub use event::Event;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{
+    CallResponse, Context, Error, GenesisState, Module, ModuleId, ModuleInfo, ModuleRestApi, Spec,
+    StateMap, TxState,
+};
+pub use sponsorship::Sponsorship;
+
+/// The transactions a sponsor agrees to pay the fees of.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Serialize,
+    Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+#[serde(bound = "S::Address: Serialize + serde::de::DeserializeOwned")]
+#[serde(rename_all = "snake_case")]
+pub struct SponsorPolicy<S: Spec> {
+    /// Names of the runtime modules whose calls are sponsored, e.g. `example_module`.
+    pub modules: Vec<String>,
+    /// The users whose transactions are sponsored.
+    #[serde(default)]
+    pub users: Vec<S::Address>,
+    /// Sponsors the transactions of every user instead of `users`, which must then be empty.
+    #[serde(default)]
+    pub allow_all_users: bool,
+    /// The gas fees the sponsor still agrees to pay. Every sponsored transaction is deducted
+    /// the fee it paid, and is rejected if its maximum fee exceeds the budget.
+    pub budget: u64,
+}
+
+/// A sponsor and its policy, as written in the genesis file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "S::Address: Serialize + serde::de::DeserializeOwned")]
+#[serde(rename_all = "snake_case")]
+pub struct SponsorConfig<S: Spec> {
+    /// The account paying the fees.
+    pub sponsor: S::Address,
+    /// The transactions it pays the fees of.
+    pub policy: SponsorPolicy<S>,
+}
+
+/// Genesis configuration of the [`Paymaster`] module.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "S::Address: Serialize + serde::de::DeserializeOwned")]
+#[serde(rename_all = "snake_case")]
+pub struct PaymasterConfig<S: Spec> {
+    /// The sponsors registered at genesis.
+    #[serde(default)]
+    pub sponsors: Vec<SponsorConfig<S>>,
+}
+
+/// Lets sponsor accounts pay the gas of other users' transactions.
+///
+/// Sponsors register a [`SponsorPolicy`] describing the transactions they pay for. A user
+/// names the sponsor in the transaction envelope; the runtime then checks the policy with
+/// [`Paymaster::request_sponsorship`] during authentication and passes the resulting
+/// [`Sponsorship`] along with the credentials of the sender. Once the sender is resolved,
+/// [`Paymaster::check_sender`] is called before the gas is reserved from the sponsor, and the
+/// fee paid is deducted from the budget with [`Paymaster::record_spending`].
+#[derive(Clone, ModuleInfo, ModuleRestApi)]
+pub struct Paymaster<S: Spec> {
+    /// Id of the module.
+    #[id]
+    pub id: ModuleId,
+
+    /// The policy of every sponsor.
+    #[state]
+    pub policies: StateMap<S::Address, SponsorPolicy<S>>,
+}
+
+impl<S: Spec> SponsorPolicy<S> {
+    /// Rejects policies which sponsor no module, or whose users are ambiguous: a policy lists
+    /// the sponsored users or sponsors everyone explicitly.
+    pub fn validate(&self) -> Result<(), PaymasterError<S::Address>> {
+        if self.modules.is_empty() {
+            return Err(PaymasterError::NoModules);
+        }
+        match (self.users.is_empty(), self.allow_all_users) {
+            (true, false) => Err(PaymasterError::NoUsers),
+            (false, true) => Err(PaymasterError::UsersWithAllowAll),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<S: Spec> Module for Paymaster<S> {
+    type Spec = S;
+
+    type Config = PaymasterConfig<S>;
+
+    type CallMessage = call::CallMessage<S>;
+
+    type Event = Event<S>;
+
+    fn genesis(
+        &self,
+        config: &Self::Config,
+        state: &mut impl GenesisState<S>,
+    ) -> Result<(), Error> {
+        Ok(self.init_module(config, state)?)
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Context<Self::Spec>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::SetPolicy {
+                modules,
+                users,
+                allow_all_users,
+                budget,
+            } => Ok(self.set_policy(
+                SponsorPolicy {
+                    modules,
+                    users,
+                    allow_all_users,
+                    budget,
+                },
+                context,
+                state,
+            )?),
+            call::CallMessage::RemovePolicy => Ok(self.remove_policy(context, state)?),
+        }
+    }
+}
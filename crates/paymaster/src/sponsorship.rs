@@ -0,0 +1,106 @@
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_modules_api::{Spec, StateReader, StateReaderAndWriter};
+use sov_state::namespaces::User;
+
+use crate::error::PaymasterError;
+use crate::{Paymaster, SponsorPolicy};
+
+/// A sponsorship accepted during authentication, added to the credentials of the sender so
+/// that the gas of the transaction is reserved from, and refunded to, the sponsor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sponsorship<S: Spec> {
+    /// The account paying for the gas.
+    pub sponsor: S::Address,
+    /// The maximum fee of the transaction, reserved from the sponsor.
+    pub max_fee: u64,
+}
+
+impl<S: Spec> Paymaster<S> {
+    /// Checks that the policy of `sponsor` covers a call to `module` with a maximum fee of
+    /// `max_fee`, and returns the sponsorship of the transaction. Nothing is written.
+    pub fn request_sponsorship(
+        &self,
+        sponsor: S::Address,
+        module: &str,
+        max_fee: u64,
+        state: &mut impl StateReader<User>,
+    ) -> anyhow::Result<Sponsorship<S>> {
+        let policy = self.policy(&sponsor, state)?;
+
+        if !policy.modules.iter().any(|sponsored| sponsored == module) {
+            return Err(PaymasterError::ModuleNotSponsored {
+                sponsor,
+                module: module.to_string(),
+            }
+            .into());
+        }
+        if max_fee > policy.budget {
+            return Err(PaymasterError::BudgetExceeded {
+                sponsor,
+                budget: policy.budget,
+                max_fee,
+            }
+            .into());
+        }
+
+        Ok(Sponsorship { sponsor, max_fee })
+    }
+
+    /// Checks that the policy of the sponsor covers `sender`, the account the transaction of
+    /// `sponsorship` was resolved to.
+    pub fn check_sender(
+        &self,
+        sponsorship: &Sponsorship<S>,
+        sender: &S::Address,
+        state: &mut impl StateReader<User>,
+    ) -> anyhow::Result<()> {
+        let policy = self.policy(&sponsorship.sponsor, state)?;
+
+        if !policy.allow_all_users && !policy.users.contains(sender) {
+            return Err(PaymasterError::UserNotSponsored {
+                sponsor: sponsorship.sponsor.clone(),
+                user: sender.clone(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Deducts `amount`, the gas fee paid for a transaction of `sponsorship`, from the budget
+    /// of the sponsor. Called once the gas of the transaction is settled, whether it succeeded
+    /// or not.
+    pub fn record_spending(
+        &self,
+        sponsorship: &Sponsorship<S>,
+        amount: u64,
+        state: &mut impl StateReaderAndWriter<User>,
+    ) {
+        if let Some(mut policy) = self
+            .policies
+            .get(&sponsorship.sponsor, state)
+            .unwrap_infallible()
+        {
+            policy.budget = policy.budget.saturating_sub(amount);
+            self.policies
+                .set(&sponsorship.sponsor, &policy, state)
+                .unwrap_infallible();
+        }
+    }
+
+    fn policy(
+        &self,
+        sponsor: &S::Address,
+        state: &mut impl StateReader<User>,
+    ) -> anyhow::Result<SponsorPolicy<S>> {
+        self.policies
+            .get(sponsor, state)
+            .unwrap_infallible()
+            .ok_or_else(|| {
+                PaymasterError::NotASponsor {
+                    sponsor: sponsor.clone(),
+                }
+                .into()
+            })
+    }
+}
@@ -0,0 +1,210 @@
+use paymaster::{CallMessage, Event, Paymaster, SponsorPolicy, Sponsorship};
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::runtime::TestRunner;
+use sov_test_utils::{generate_optimistic_runtime, AsUser, TestUser, TransactionTestCase};
+
+generate_optimistic_runtime!(PaymasterRuntime <= paymaster: Paymaster<S>);
+
+type S = sov_test_utils::TestSpec;
+
+fn setup() -> (
+    TestUser<S>,
+    TestUser<S>,
+    TestUser<S>,
+    TestRunner<PaymasterRuntime<S>, S>,
+) {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(3);
+
+    let sponsor = genesis_config.additional_accounts[0].clone();
+    let user = genesis_config.additional_accounts[1].clone();
+    let other = genesis_config.additional_accounts[2].clone();
+
+    let genesis = GenesisConfig::from_minimal_config(
+        genesis_config.into(),
+        paymaster::PaymasterConfig { sponsors: vec![] },
+    );
+
+    let runner =
+        TestRunner::new_with_genesis(genesis.into_genesis_params(), PaymasterRuntime::default());
+
+    (sponsor, user, other, runner)
+}
+
+const BUDGET: u64 = 1_000;
+
+fn set_policy(
+    modules: &[&str],
+    users: Vec<<S as sov_modules_api::Spec>::Address>,
+    allow_all_users: bool,
+) -> CallMessage<S> {
+    CallMessage::SetPolicy {
+        modules: modules.iter().map(|module| module.to_string()).collect(),
+        users,
+        allow_all_users,
+        budget: BUDGET,
+    }
+}
+
+#[test]
+fn test_set_policy_and_sponsor() {
+    let (sponsor, user, other, mut runner) = setup();
+    let sponsor_address = sponsor.address();
+    let user_address = user.address();
+
+    runner.execute_transaction(TransactionTestCase {
+        input: sponsor.create_plain_message::<Paymaster<S>>(set_policy(
+            &["example_module"],
+            vec![user.address()],
+            false,
+        )),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                result.events,
+                vec![PaymasterRuntimeEvent::Paymaster(Event::PolicySet {
+                    sponsor: sponsor_address,
+                    policy: SponsorPolicy {
+                        modules: vec!["example_module".to_string()],
+                        users: vec![user_address],
+                        allow_all_users: false,
+                        budget: BUDGET,
+                    },
+                })]
+            );
+        }),
+    });
+
+    let sponsor_address = sponsor.address();
+    let user_address = user.address();
+    let other_address = other.address();
+    runner.query_state(|state| {
+        let paymaster = Paymaster::<S>::default();
+
+        // Not a sponsor.
+        assert!(paymaster
+            .request_sponsorship(other_address.clone(), "example_module", 10, state)
+            .is_err());
+        // Module out of scope.
+        assert!(paymaster
+            .request_sponsorship(sponsor_address.clone(), "bank", 10, state)
+            .is_err());
+        // Above the budget.
+        assert!(paymaster
+            .request_sponsorship(sponsor_address.clone(), "example_module", BUDGET + 1, state)
+            .is_err());
+
+        let sponsorship = paymaster
+            .request_sponsorship(sponsor_address.clone(), "example_module", 600, state)
+            .unwrap();
+        assert_eq!(
+            sponsorship,
+            Sponsorship {
+                sponsor: sponsor_address.clone(),
+                max_fee: 600,
+            }
+        );
+        assert!(paymaster
+            .check_sender(&sponsorship, &user_address, state)
+            .is_ok());
+        // The sender is not covered by the policy.
+        assert!(paymaster
+            .check_sender(&sponsorship, &other_address, state)
+            .is_err());
+
+        // The fee paid is deducted from the budget.
+        paymaster.record_spending(&sponsorship, 600, state);
+        assert!(paymaster
+            .request_sponsorship(sponsor_address.clone(), "example_module", 401, state)
+            .is_err());
+        assert!(paymaster
+            .request_sponsorship(sponsor_address, "example_module", 400, state)
+            .is_ok());
+    });
+}
+
+#[test]
+fn test_allow_all_users() {
+    let (sponsor, _, other, mut runner) = setup();
+
+    runner.execute_transaction(TransactionTestCase {
+        input: sponsor.create_plain_message::<Paymaster<S>>(set_policy(
+            &["example_module"],
+            vec![],
+            true,
+        )),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    let sponsor_address = sponsor.address();
+    let other_address = other.address();
+    runner.query_state(|state| {
+        let paymaster = Paymaster::<S>::default();
+        let sponsorship = paymaster
+            .request_sponsorship(sponsor_address, "example_module", 10, state)
+            .unwrap();
+        assert!(paymaster
+            .check_sender(&sponsorship, &other_address, state)
+            .is_ok());
+    });
+}
+
+#[test]
+fn test_remove_policy() {
+    let (sponsor, user, _, mut runner) = setup();
+
+    runner.execute_transaction(TransactionTestCase {
+        input: sponsor.create_plain_message::<Paymaster<S>>(set_policy(
+            &["example_module"],
+            vec![],
+            true,
+        )),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    // Only sponsors can remove their policy.
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<Paymaster<S>>(CallMessage::RemovePolicy),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: sponsor.create_plain_message::<Paymaster<S>>(CallMessage::RemovePolicy),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    let sponsor_address = sponsor.address();
+    runner.query_state(|state| {
+        let paymaster = Paymaster::<S>::default();
+        assert!(paymaster
+            .request_sponsorship(sponsor_address, "example_module", 10, state)
+            .is_err());
+    });
+}
+
+#[test]
+fn test_invalid_policies_are_rejected() {
+    let (sponsor, user, _, mut runner) = setup();
+
+    for message in [
+        set_policy(&[], vec![user.address()], false),
+        // An empty list of users does not sponsor everyone implicitly.
+        set_policy(&["example_module"], vec![], false),
+        set_policy(&["example_module"], vec![user.address()], true),
+    ] {
+        runner.execute_transaction(TransactionTestCase {
+            input: sponsor.create_plain_message::<Paymaster<S>>(message),
+            assert: Box::new(|result, _state| {
+                assert!(result.tx_receipt.is_reverted());
+            }),
+        });
+    }
+}
@@ -20,5 +20,20 @@ fn main() {
 }
 
 fn get_guest_options() -> HashMap<&'static str, risc0_build::GuestOptions> {
-    HashMap::new()
+    let mut guest_pkg_to_options = HashMap::new();
+
+    // The guests must run the same kernel as the native node, or their proofs won't match.
+    if std::env::var("CARGO_FEATURE_SOFT_CONFIRMATIONS").is_ok() {
+        for guest in ["sov-demo-prover-guest-celestia", "guest-mock-starter"] {
+            guest_pkg_to_options.insert(
+                guest,
+                risc0_build::GuestOptions {
+                    features: vec!["soft-confirmations".to_string()],
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    guest_pkg_to_options
 }
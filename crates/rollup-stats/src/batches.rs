@@ -0,0 +1,71 @@
+//! Senders of the batches of the slot being executed.
+//!
+//! The runtime reports the sender of a batch from `begin_batch_hook` and its outcome from
+//! `end_batch_hook`. Whatever `begin_batch_hook` writes to the state is reverted with the batch
+//! when the hook rejects it, so the senders are kept in memory by the module instead, and the
+//! rejected batches are only written to the state when the slot is finalized.
+use std::sync::{Arc, Mutex};
+
+struct Senders<Address> {
+    /// Sender of the batch being applied.
+    current: Option<Address>,
+    /// Senders of the batches rejected during the slot, in order.
+    rejected: Vec<Address>,
+}
+
+/// The senders of the batches of the slot being executed, shared by the clones of the module.
+pub struct SlotSenders<Address> {
+    senders: Arc<Mutex<Senders<Address>>>,
+}
+
+impl<Address> Default for SlotSenders<Address> {
+    fn default() -> Self {
+        Self {
+            senders: Arc::new(Mutex::new(Senders {
+                current: None,
+                rejected: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl<Address> Clone for SlotSenders<Address> {
+    fn clone(&self) -> Self {
+        Self {
+            senders: self.senders.clone(),
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "native"), allow(dead_code))]
+impl<Address> SlotSenders<Address> {
+    /// Forgets the senders of the previous slot.
+    pub(crate) fn begin_slot(&self) {
+        let mut senders = self.senders.lock().expect("lock poisoned");
+        senders.current = None;
+        senders.rejected.clear();
+    }
+
+    /// Remembers the sender of the batch about to be applied.
+    pub(crate) fn begin_batch(&self, sender: Address) {
+        self.senders.lock().expect("lock poisoned").current = Some(sender);
+    }
+
+    /// Accounts the batch being applied as rejected.
+    pub(crate) fn reject_batch(&self) {
+        let mut senders = self.senders.lock().expect("lock poisoned");
+        if let Some(sender) = senders.current.take() {
+            senders.rejected.push(sender);
+        }
+    }
+
+    /// The sender of the batch being applied, if any.
+    pub(crate) fn end_batch(&self) -> Option<Address> {
+        self.senders.lock().expect("lock poisoned").current.take()
+    }
+
+    /// The senders of the batches rejected during the slot.
+    pub(crate) fn take_rejected(&self) -> Vec<Address> {
+        std::mem::take(&mut self.senders.lock().expect("lock poisoned").rejected)
+    }
+}
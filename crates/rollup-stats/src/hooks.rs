@@ -0,0 +1,118 @@
+//! Entry points called by the runtime hooks.
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_modules_api::{DaSpec, Spec, StateReaderAndWriter};
+use sov_state::namespaces::Accessory;
+
+use crate::{BatchOutcome, RollupStats, SequencerStats, SlotStats};
+
+impl<S: Spec> RollupStats<S> {
+    /// Starts collecting the statistics of slot `slot_number`, the rollup height of the slot
+    /// as counted by the `chain_state` module.
+    pub fn begin_slot(&self, slot_number: u64, state: &mut impl StateReaderAndWriter<Accessory>) {
+        crate::SlotMetrics::get().begin_slot();
+        self.batch_senders.begin_slot();
+
+        self.current_slot
+            .set(
+                &SlotStats {
+                    slot_number,
+                    ..Default::default()
+                },
+                state,
+            )
+            .unwrap_infallible();
+    }
+
+    /// Remembers the sender of the batch about to be applied. Nothing is written to the state,
+    /// as the batch hooks' writes are reverted with a rejected batch.
+    pub fn begin_batch(&self, sender: &<S::Da as DaSpec>::Address) {
+        self.batch_senders.begin_batch(sender.clone());
+    }
+
+    /// Accounts for the batch started by [`RollupStats::begin_batch`] as rejected before any of
+    /// its transactions was processed. It is written to the state by
+    /// [`RollupStats::finalize_slot`].
+    pub fn reject_batch(&self) {
+        self.batch_senders.reject_batch();
+    }
+
+    /// Accounts for the batch started by [`RollupStats::begin_batch`]. `tx_gas_used` holds
+    /// the gas used by each transaction of the batch. Only applied batches, and their
+    /// transactions, are counted in the statistics of the slot and of the sequencer.
+    pub fn record_batch<'a>(
+        &self,
+        outcome: BatchOutcome,
+        tx_gas_used: impl IntoIterator<Item = &'a [u64]>,
+        state: &mut impl StateReaderAndWriter<Accessory>,
+    ) {
+        let mut batch = SlotStats::default();
+        if outcome.is_applied() {
+            batch.batches = 1;
+            for gas_used in tx_gas_used {
+                batch.txs += 1;
+                batch.add_gas(gas_used);
+            }
+        }
+
+        let mut stats = self
+            .current_slot
+            .get(state)
+            .unwrap_infallible()
+            .unwrap_or_default();
+        stats.batches += batch.batches;
+        stats.txs += batch.txs;
+        stats.add_gas(&batch.gas_used);
+        self.current_slot.set(&stats, state).unwrap_infallible();
+
+        if let Some(sender) = self.batch_senders.end_batch() {
+            self.update_sequencer(&sender, state, |stats| {
+                stats.record(outcome, batch.txs, &batch.gas_used)
+            });
+        }
+    }
+
+    /// Stores the statistics of the current slot together with its final state root, and the
+    /// batches rejected during the slot.
+    pub fn finalize_slot(
+        &self,
+        state_root: Vec<u8>,
+        state: &mut impl StateReaderAndWriter<Accessory>,
+    ) {
+        for sender in self.batch_senders.take_rejected() {
+            self.update_sequencer(&sender, state, |stats| stats.batches_rejected += 1);
+        }
+
+        let mut stats = self
+            .current_slot
+            .get(state)
+            .unwrap_infallible()
+            .unwrap_or_default();
+        stats.state_root = state_root;
+        crate::SlotMetrics::get().finalize_slot(&stats);
+
+        self.slots
+            .set(&stats.slot_number, &stats, state)
+            .unwrap_infallible();
+        self.latest_slot
+            .set(&stats.slot_number, state)
+            .unwrap_infallible();
+        self.current_slot.delete(state).unwrap_infallible();
+    }
+
+    fn update_sequencer(
+        &self,
+        sender: &<S::Da as DaSpec>::Address,
+        state: &mut impl StateReaderAndWriter<Accessory>,
+        update: impl FnOnce(&mut SequencerStats),
+    ) {
+        let mut stats = self
+            .sequencers
+            .get(sender, state)
+            .unwrap_infallible()
+            .unwrap_or_default();
+        update(&mut stats);
+        self.sequencers
+            .set(sender, &stats, state)
+            .unwrap_infallible();
+    }
+}
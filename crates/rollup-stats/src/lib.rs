@@ -0,0 +1,81 @@
+mod batches;
+#[cfg(feature = "native")]
+mod hooks;
+#[cfg(feature = "native")]
+mod metrics;
+#[cfg(feature = "native")]
+mod query;
+mod sequencer;
+mod slot;
+use batches::SlotSenders;
+#[cfg(feature = "native")]
+pub use metrics::SlotMetrics;
+#[cfg(feature = "native")]
+pub use query::*;
+pub use sequencer::{BatchOutcome, SequencerStats};
+pub use slot::SlotStats;
+use sov_modules_api::{
+    AccessoryStateMap, AccessoryStateValue, CallResponse, Context, DaSpec, Error, GenesisState,
+    Module, ModuleId, ModuleInfo, ModuleRestApi, NotInstantiable, Spec, TxState,
+};
+
+/// Collects statistics about the rollup's activity from the runtime hooks.
+///
+/// Everything is kept in accessory state, which is not part of the state root. The entry
+/// points called by the hooks only exist with the `native` feature: the zk guests are built
+/// without it and the runtime skips them, so the statistics are only maintained by native
+/// nodes. The module has no call messages and no genesis configuration.
+#[derive(Clone, ModuleInfo, ModuleRestApi)]
+pub struct RollupStats<S: Spec> {
+    /// Id of the module.
+    #[id]
+    pub id: ModuleId,
+
+    /// Statistics of the slot currently being executed.
+    #[state]
+    pub current_slot: AccessoryStateValue<SlotStats>,
+
+    /// Statistics of every finalized slot, by slot number.
+    #[state]
+    pub slots: AccessoryStateMap<u64, SlotStats>,
+
+    /// Number of the latest finalized slot.
+    #[state]
+    pub latest_slot: AccessoryStateValue<u64>,
+
+    /// Aggregated statistics of every sequencer that submitted a batch.
+    #[state]
+    pub sequencers: AccessoryStateMap<<S::Da as DaSpec>::Address, SequencerStats>,
+
+    /// Senders of the batches of the slot being executed. Not part of the state, so that the
+    /// senders of rejected batches survive the revert of the batch.
+    #[phantom]
+    batch_senders: SlotSenders<<S::Da as DaSpec>::Address>,
+}
+
+impl<S: Spec> Module for RollupStats<S> {
+    type Spec = S;
+
+    type Config = ();
+
+    type CallMessage = NotInstantiable;
+
+    type Event = ();
+
+    fn genesis(
+        &self,
+        _config: &Self::Config,
+        _state: &mut impl GenesisState<S>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        _context: &Context<Self::Spec>,
+        _state: &mut impl TxState<S>,
+    ) -> Result<CallResponse, Error> {
+        match msg {}
+    }
+}
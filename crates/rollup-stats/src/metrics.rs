@@ -0,0 +1,136 @@
+//! Prometheus metrics of the slots executed by a native node.
+//!
+//! The metrics are registered in the default Prometheus registry, served by the metrics
+//! endpoint of the node. The zk guests are built without the `native` feature and never
+//! record them.
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use prometheus::{
+    exponential_buckets, register_histogram, register_int_counter_vec, register_int_gauge,
+    Histogram, IntCounterVec, IntGauge,
+};
+
+use crate::SlotStats;
+
+/// The metrics updated by the hooks of [`crate::RollupStats`] and by the node when it
+/// sees an aggregated proof.
+pub struct SlotMetrics {
+    /// Rollup height of the latest finalized slot, as counted by the `chain_state` module.
+    pub synced_height: IntGauge,
+    /// Time from the beginning of a slot to its finalization, in seconds.
+    pub slot_processing_seconds: Histogram,
+    /// Transactions applied per slot.
+    pub txs_per_slot: Histogram,
+    /// Batches applied per slot.
+    pub batches_per_slot: Histogram,
+    /// Gas used by the transactions, by gas dimension.
+    pub gas_used: IntCounterVec,
+    /// Finalized slots not yet covered by an aggregated proof.
+    pub prover_queue_depth: IntGauge,
+    /// Time from the finalization of a slot to the aggregated proof covering it, in seconds.
+    pub proof_latency_seconds: Histogram,
+    slot_started_at: Mutex<Option<Instant>>,
+    /// When each unproven slot was finalized, by rollup height.
+    unproven_slots: Mutex<BTreeMap<u64, Instant>>,
+}
+
+/// The most unproven slots tracked. A node without a prover never sees a proof, so only the
+/// latest slots are kept, and [`SlotMetrics::prover_queue_depth`] stops growing there.
+const MAX_UNPROVEN_SLOTS: usize = 100_000;
+
+impl SlotMetrics {
+    /// The metrics, registered on first use.
+    pub fn get() -> &'static Self {
+        static METRICS: OnceLock<SlotMetrics> = OnceLock::new();
+        METRICS.get_or_init(Self::register)
+    }
+
+    fn register() -> Self {
+        let count_buckets = exponential_buckets(1.0, 2.0, 12).expect("valid buckets");
+        Self {
+            synced_height: register_int_gauge!(
+                "rollup_synced_height",
+                "Rollup height of the latest finalized slot, as counted by the chain state"
+            )
+            .expect("registered once"),
+            slot_processing_seconds: register_histogram!(
+                "rollup_slot_processing_seconds",
+                "Time from the beginning of a slot to its finalization"
+            )
+            .expect("registered once"),
+            txs_per_slot: register_histogram!(
+                "rollup_txs_per_slot",
+                "Transactions applied per slot",
+                count_buckets.clone()
+            )
+            .expect("registered once"),
+            batches_per_slot: register_histogram!(
+                "rollup_batches_per_slot",
+                "Batches applied per slot",
+                count_buckets
+            )
+            .expect("registered once"),
+            gas_used: register_int_counter_vec!(
+                "rollup_gas_used_total",
+                "Gas used by the transactions, by gas dimension",
+                &["dimension"]
+            )
+            .expect("registered once"),
+            prover_queue_depth: register_int_gauge!(
+                "rollup_prover_queue_depth",
+                "Finalized slots not yet covered by an aggregated proof"
+            )
+            .expect("registered once"),
+            proof_latency_seconds: register_histogram!(
+                "rollup_proof_latency_seconds",
+                "Time from the finalization of a slot to the aggregated proof covering it",
+                exponential_buckets(1.0, 2.0, 14).expect("valid buckets")
+            )
+            .expect("registered once"),
+            slot_started_at: Mutex::new(None),
+            unproven_slots: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub(crate) fn begin_slot(&self) {
+        *self.slot_started_at.lock().expect("lock poisoned") = Some(Instant::now());
+    }
+
+    pub(crate) fn finalize_slot(&self, stats: &SlotStats) {
+        if let Some(started_at) = self.slot_started_at.lock().expect("lock poisoned").take() {
+            self.slot_processing_seconds
+                .observe(started_at.elapsed().as_secs_f64());
+        }
+        self.synced_height
+            .set(i64::try_from(stats.slot_number).unwrap_or(i64::MAX));
+        self.txs_per_slot.observe(stats.txs as f64);
+        self.batches_per_slot.observe(stats.batches as f64);
+        for (dimension, gas) in stats.gas_used.iter().enumerate() {
+            self.gas_used
+                .with_label_values(&[&dimension.to_string()])
+                .inc_by(*gas);
+        }
+
+        let mut unproven_slots = self.unproven_slots.lock().expect("lock poisoned");
+        unproven_slots.insert(stats.slot_number, Instant::now());
+        while unproven_slots.len() > MAX_UNPROVEN_SLOTS {
+            unproven_slots.pop_first();
+        }
+        self.prover_queue_depth.set(unproven_slots.len() as i64);
+    }
+
+    /// Records an aggregated proof covering the slots up to `final_slot_number`, a rollup
+    /// height. The later slots stay in the prover queue.
+    pub fn record_proof(&self, final_slot_number: u64) {
+        let mut unproven_slots = self.unproven_slots.lock().expect("lock poisoned");
+        let later_slots = unproven_slots.split_off(&final_slot_number.saturating_add(1));
+        let proven_slots = std::mem::replace(&mut *unproven_slots, later_slots);
+        for finalized_at in proven_slots.values() {
+            self.proof_latency_seconds
+                .observe(finalized_at.elapsed().as_secs_f64());
+        }
+        self.prover_queue_depth.set(unproven_slots.len() as i64);
+    }
+}
@@ -0,0 +1,78 @@
+use axum::routing::get;
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_modules_api::rest::utils::{errors, ApiResult, Path};
+use sov_modules_api::rest::{ApiState, HasCustomRestApi};
+use sov_modules_api::{ApiStateAccessor, DaSpec, Spec};
+
+use crate::{RollupStats, SequencerStats, SlotStats};
+
+impl<S: Spec> RollupStats<S> {
+    /// Returns the statistics of the finalized slot `slot_number`, if any.
+    pub fn query_slot(
+        &self,
+        slot_number: u64,
+        state: &mut ApiStateAccessor<S>,
+    ) -> Option<SlotStats> {
+        self.slots.get(&slot_number, state).unwrap_infallible()
+    }
+
+    /// Returns the statistics of the latest finalized slot, if any.
+    pub fn query_latest_slot(&self, state: &mut ApiStateAccessor<S>) -> Option<SlotStats> {
+        let slot_number = self.latest_slot.get(state).unwrap_infallible()?;
+        self.query_slot(slot_number, state)
+    }
+
+    /// Returns the aggregated statistics of the sequencer with DA address `sequencer`, if it
+    /// ever submitted a batch.
+    pub fn query_sequencer(
+        &self,
+        sequencer: &<S::Da as DaSpec>::Address,
+        state: &mut ApiStateAccessor<S>,
+    ) -> Option<SequencerStats> {
+        self.sequencers.get(sequencer, state).unwrap_infallible()
+    }
+
+    async fn route_slot(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+        Path(slot_number): Path<u64>,
+    ) -> ApiResult<SlotStats> {
+        let stats = state
+            .query_slot(slot_number, &mut accessor)
+            .ok_or_else(|| errors::not_found_404("Slot stats", slot_number))?;
+        Ok(stats.into())
+    }
+
+    async fn route_latest_slot(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+    ) -> ApiResult<SlotStats> {
+        let stats = state
+            .query_latest_slot(&mut accessor)
+            .ok_or_else(|| errors::not_found_404("Slot stats", "latest"))?;
+        Ok(stats.into())
+    }
+
+    async fn route_sequencer(
+        state: ApiState<S, Self>,
+        mut accessor: ApiStateAccessor<S>,
+        Path(sequencer): Path<<S::Da as DaSpec>::Address>,
+    ) -> ApiResult<SequencerStats> {
+        let stats = state
+            .query_sequencer(&sequencer, &mut accessor)
+            .ok_or_else(|| errors::not_found_404("Sequencer stats", sequencer))?;
+        Ok(stats.into())
+    }
+}
+
+impl<S: Spec> HasCustomRestApi for RollupStats<S> {
+    type Spec = S;
+
+    fn custom_rest_api(&self, state: ApiState<S>) -> axum::Router<()> {
+        axum::Router::new()
+            .route("/slots/latest", get(Self::route_latest_slot))
+            .route("/slots/:slotNumber", get(Self::route_slot))
+            .route("/sequencers/:daAddress", get(Self::route_sequencer))
+            .with_state(state.with(self.clone()))
+    }
+}
@@ -0,0 +1,77 @@
+use crate::slot::add_gas;
+
+/// What happened to a batch, as reported by the runtime.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BatchOutcome {
+    /// The batch was applied and the sequencer was rewarded `amount`.
+    Rewarded(u64),
+    /// The batch was applied and the sequencer was penalized `amount`.
+    Penalized(u64),
+    /// The batch was rejected and the sequencer was slashed.
+    Slashed,
+    /// The batch was rejected without touching the sequencer's stake.
+    Ignored,
+}
+
+impl BatchOutcome {
+    /// Returns `true` if the batch was applied, whether the sequencer was rewarded or
+    /// penalized.
+    pub fn is_applied(&self) -> bool {
+        matches!(self, BatchOutcome::Rewarded(_) | BatchOutcome::Penalized(_))
+    }
+}
+
+/// Aggregated activity of a sequencer since genesis.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct SequencerStats {
+    /// Number of batches applied.
+    pub batches_accepted: u64,
+    /// Number of batches rejected, either by the runtime's sequencer policy or because the
+    /// sequencer was slashed or ignored.
+    pub batches_rejected: u64,
+    /// Number of times the sequencer was slashed.
+    pub slashings: u64,
+    /// Sum of the rewards paid to the sequencer.
+    pub rewards: u64,
+    /// Sum of the penalties charged to the sequencer.
+    pub penalties: u64,
+    /// Number of transactions included in the applied batches.
+    pub txs: u64,
+    /// Gas used by those transactions, per gas dimension.
+    pub gas_used: Vec<u64>,
+}
+
+impl SequencerStats {
+    /// Accounts for a batch whose outcome is `outcome`.
+    pub fn record(&mut self, outcome: BatchOutcome, txs: u64, gas_used: &[u64]) {
+        match outcome {
+            BatchOutcome::Rewarded(amount) => {
+                self.batches_accepted += 1;
+                self.rewards = self.rewards.saturating_add(amount);
+            }
+            BatchOutcome::Penalized(amount) => {
+                self.batches_accepted += 1;
+                self.penalties = self.penalties.saturating_add(amount);
+            }
+            BatchOutcome::Slashed => {
+                self.batches_rejected += 1;
+                self.slashings += 1;
+            }
+            BatchOutcome::Ignored => self.batches_rejected += 1,
+        }
+
+        self.txs = self.txs.saturating_add(txs);
+        add_gas(&mut self.gas_used, gas_used);
+    }
+}
@@ -0,0 +1,42 @@
+/// Activity of the rollup during a single slot.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct SlotStats {
+    /// The rollup height of the slot.
+    pub slot_number: u64,
+    /// Number of batches applied during the slot.
+    pub batches: u64,
+    /// Number of transactions included in those batches.
+    pub txs: u64,
+    /// Gas used by those transactions, per gas dimension.
+    pub gas_used: Vec<u64>,
+    /// The state root after the slot, as serialized by the storage.
+    pub state_root: Vec<u8>,
+}
+
+impl SlotStats {
+    /// Adds `gas` to [`SlotStats::gas_used`], dimension by dimension.
+    pub fn add_gas(&mut self, gas: &[u64]) {
+        add_gas(&mut self.gas_used, gas);
+    }
+}
+
+/// Adds `gas` to `total`, dimension by dimension, saturating on overflow.
+pub(crate) fn add_gas(total: &mut Vec<u64>, gas: &[u64]) {
+    if total.len() < gas.len() {
+        total.resize(gas.len(), 0);
+    }
+    for (total, used) in total.iter_mut().zip(gas) {
+        *total = total.saturating_add(*used);
+    }
+}
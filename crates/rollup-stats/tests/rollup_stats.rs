@@ -0,0 +1,177 @@
+use rollup_stats::{BatchOutcome, RollupStats, SequencerStats, SlotMetrics, SlotStats};
+use sov_mock_da::MockAddress;
+use sov_test_utils::generate_optimistic_runtime;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::runtime::TestRunner;
+
+generate_optimistic_runtime!(RollupStatsRuntime <= rollup_stats: RollupStats<S>);
+
+type S = sov_test_utils::TestSpec;
+
+fn setup() -> TestRunner<RollupStatsRuntime<S>, S> {
+    let genesis_config = HighLevelOptimisticGenesisConfig::generate();
+    let genesis = GenesisConfig::from_minimal_config(genesis_config.into(), ());
+
+    TestRunner::new_with_genesis(genesis.into_genesis_params(), RollupStatsRuntime::default())
+}
+
+#[test]
+fn test_add_gas() {
+    let mut stats = SlotStats::default();
+
+    stats.add_gas(&[10, 20]);
+    stats.add_gas(&[1]);
+    stats.add_gas(&[1, 2, 3]);
+    assert_eq!(stats.gas_used, vec![12, 22, 3]);
+
+    stats.add_gas(&[u64::MAX]);
+    assert_eq!(stats.gas_used, vec![u64::MAX, 22, 3]);
+}
+
+#[test]
+fn test_slot_stats() {
+    let mut runner = setup();
+
+    runner.query_state(|state| {
+        let stats = RollupStats::<S>::default();
+        assert_eq!(stats.query_latest_slot(state), None);
+        assert_eq!(stats.query_slot(0, state), None);
+
+        stats.begin_slot(1, state);
+        stats.record_batch(
+            BatchOutcome::Rewarded(1),
+            [[10, 5].as_slice(), [2, 1].as_slice()],
+            state,
+        );
+        stats.record_batch(BatchOutcome::Ignored, [[3, 3].as_slice()], state);
+        stats.record_batch(BatchOutcome::Slashed, [], state);
+        stats.record_batch(BatchOutcome::Penalized(1), [], state);
+        stats.finalize_slot(vec![1; 32], state);
+
+        stats.begin_slot(2, state);
+        stats.finalize_slot(vec![2; 32], state);
+
+        assert_eq!(
+            stats.query_slot(1, state),
+            // The ignored and slashed batches were not applied.
+            Some(SlotStats {
+                slot_number: 1,
+                batches: 2,
+                txs: 2,
+                gas_used: vec![12, 6],
+                state_root: vec![1; 32],
+            })
+        );
+        assert_eq!(
+            stats.query_latest_slot(state),
+            Some(SlotStats {
+                slot_number: 2,
+                batches: 0,
+                txs: 0,
+                gas_used: vec![],
+                state_root: vec![2; 32],
+            })
+        );
+        assert_eq!(stats.query_slot(3, state), None);
+    });
+}
+
+#[test]
+fn test_slot_metrics() {
+    let mut runner = setup();
+    // The metrics are global and other tests finalize slots concurrently: only check that
+    // they grew by at least this slot.
+    let metrics = SlotMetrics::get();
+    let slots = metrics.txs_per_slot.get_sample_count();
+    let gas_used = metrics.gas_used.with_label_values(&["0"]).get();
+
+    runner.query_state(|state| {
+        let stats = RollupStats::<S>::default();
+        stats.begin_slot(1, state);
+        stats.record_batch(BatchOutcome::Rewarded(1), [[10, 5].as_slice()], state);
+        stats.finalize_slot(vec![1; 32], state);
+    });
+
+    assert!(metrics.txs_per_slot.get_sample_count() > slots);
+    assert!(metrics.slot_processing_seconds.get_sample_count() > 0);
+    assert!(metrics.gas_used.with_label_values(&["0"]).get() >= gas_used + 10);
+    assert!(metrics.synced_height.get() > 0);
+    assert!(metrics.prover_queue_depth.get() > 0);
+
+    // Only the slots up to the end of the proof leave the queue.
+    let proofs = metrics.proof_latency_seconds.get_sample_count();
+    runner.query_state(|state| {
+        let stats = RollupStats::<S>::default();
+        stats.begin_slot(u64::MAX, state);
+        stats.finalize_slot(vec![2; 32], state);
+    });
+    metrics.record_proof(1);
+    assert!(metrics.proof_latency_seconds.get_sample_count() > proofs);
+    assert!(metrics.prover_queue_depth.get() > 0);
+
+    metrics.record_proof(u64::MAX);
+}
+
+#[test]
+fn test_sequencer_stats() {
+    let mut runner = setup();
+
+    let sequencer = MockAddress::new([1; 32]);
+    let other = MockAddress::new([2; 32]);
+
+    runner.query_state(|state| {
+        let stats = RollupStats::<S>::default();
+        assert_eq!(stats.query_sequencer(&sequencer, state), None);
+
+        stats.begin_slot(1, state);
+
+        stats.begin_batch(&sequencer);
+        stats.record_batch(BatchOutcome::Rewarded(10), [[4, 2].as_slice()], state);
+
+        stats.begin_batch(&sequencer);
+        stats.record_batch(
+            BatchOutcome::Penalized(3),
+            [[1, 1].as_slice(), [1, 1].as_slice()],
+            state,
+        );
+
+        stats.begin_batch(&sequencer);
+        stats.record_batch(BatchOutcome::Slashed, [], state);
+
+        stats.begin_batch(&sequencer);
+        stats.reject_batch();
+
+        // Rejected batches are only written when the slot is finalized.
+        assert_eq!(
+            stats
+                .query_sequencer(&sequencer, state)
+                .map(|stats| stats.batches_rejected),
+            Some(1)
+        );
+
+        stats.begin_batch(&other);
+        stats.record_batch(BatchOutcome::Rewarded(7), [], state);
+        stats.finalize_slot(vec![1; 32], state);
+
+        assert_eq!(
+            stats.query_sequencer(&sequencer, state),
+            Some(SequencerStats {
+                batches_accepted: 2,
+                batches_rejected: 2,
+                slashings: 1,
+                rewards: 10,
+                penalties: 3,
+                txs: 3,
+                gas_used: vec![6, 4],
+            })
+        );
+        assert_eq!(
+            stats.query_sequencer(&other, state),
+            Some(SequencerStats {
+                batches_accepted: 1,
+                rewards: 7,
+                ..Default::default()
+            })
+        );
+    });
+}
@@ -0,0 +1,128 @@
+//! This binary manages the genesis files of the rollup.
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use sov_modules_rollup_blueprint::RollupBlueprint;
+use sov_rollup_interface::execution_mode::Native;
+#[cfg(all(feature = "celestia_da", not(feature = "mock_da")))]
+use sov_rollup_starter::celestia_rollup::CelestiaRollup as StarterRollup;
+use sov_rollup_starter::genesis_generator::NetworkSpec;
+#[cfg(all(feature = "mock_da", not(feature = "celestia_da")))]
+use sov_rollup_starter::mock_rollup::MockRollup as StarterRollup;
+use stf_starter::genesis_config::{GenesisDocument, GenesisPaths, GenesisSource};
+use stf_starter::genesis_validation::validate_genesis;
+
+#[cfg(all(feature = "mock_da", not(feature = "celestia_da")))]
+const DEFAULT_CONFIG_PATH: &str = "../../rollup_config.toml";
+#[cfg(all(feature = "mock_da", not(feature = "celestia_da")))]
+const DEFAULT_GENESIS_PATH: &str = "../../test-data/genesis/mock/";
+
+#[cfg(all(feature = "celestia_da", not(feature = "mock_da")))]
+const DEFAULT_CONFIG_PATH: &str = "../../celestia_rollup_config.toml";
+#[cfg(all(feature = "celestia_da", not(feature = "mock_da")))]
+const DEFAULT_GENESIS_PATH: &str = "../../test-data/genesis/celestia/";
+
+type StarterSpec = <StarterRollup<Native> as RollupBlueprint<Native>>::Spec;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Checks a genesis, reporting every problem found.
+    Validate {
+        /// A directory with one file per module, or a single JSON or TOML file.
+        #[arg(long, default_value = DEFAULT_GENESIS_PATH)]
+        genesis: PathBuf,
+    },
+    /// Exports the genesis files of a directory into a single file keyed by module name.
+    Export {
+        /// The directory containing the genesis files.
+        #[arg(long, default_value = DEFAULT_GENESIS_PATH)]
+        genesis_dir: PathBuf,
+        /// The file to write, as TOML if it has a `.toml` extension and as JSON otherwise.
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Generates the genesis, node config and keys of a new network from a spec, see
+    /// `sov_rollup_starter::genesis_generator`.
+    Generate {
+        /// The TOML spec of the network.
+        #[arg(long)]
+        spec: PathBuf,
+        /// The directory to write the genesis files to, e.g. `test-data/genesis/<network>`.
+        #[arg(long)]
+        output_dir: PathBuf,
+        /// The genesis providing the parameters the spec does not cover.
+        #[arg(long, default_value = DEFAULT_GENESIS_PATH)]
+        base_genesis: PathBuf,
+        /// The node config providing the settings the spec does not cover.
+        #[arg(long, default_value = DEFAULT_CONFIG_PATH)]
+        base_rollup_config: PathBuf,
+        /// Where to write the node config, defaults to `<output-dir>/rollup_config.toml`.
+        #[arg(long)]
+        rollup_config: Option<PathBuf>,
+        /// Where to write the generated keys, defaults to `<output-dir>/keys`.
+        #[arg(long)]
+        keys_dir: Option<PathBuf>,
+        /// Overwrites the existing genesis files, node config and key files.
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+fn main() -> anyhow::Result<()> {
+    match Args::parse().command {
+        Command::Validate { genesis } => {
+            validate_genesis::<StarterSpec>(&GenesisSource::from_path(&genesis))?;
+            println!("The genesis in {} is valid", genesis.display());
+        }
+        Command::Export {
+            genesis_dir,
+            output,
+        } => {
+            let source = GenesisSource::Dir(GenesisPaths::from_dir(&genesis_dir));
+            validate_genesis::<StarterSpec>(&source)?;
+            GenesisDocument::read(&source)?.write_file(&output)?;
+            println!(
+                "Exported the genesis in {} to {}",
+                genesis_dir.display(),
+                output.display()
+            );
+        }
+        Command::Generate {
+            spec,
+            output_dir,
+            base_genesis,
+            base_rollup_config,
+            rollup_config,
+            keys_dir,
+            force,
+        } => {
+            let network = NetworkSpec::from_toml_path(&spec)?.generate::<StarterSpec>(
+                &GenesisSource::from_path(&base_genesis),
+                &base_rollup_config,
+            )?;
+            let rollup_config =
+                rollup_config.unwrap_or_else(|| output_dir.join("rollup_config.toml"));
+            let keys_dir = keys_dir.unwrap_or_else(|| output_dir.join("keys"));
+            network.write(&output_dir, &rollup_config, &keys_dir, force)?;
+
+            println!("Generated the genesis in {}", output_dir.display());
+            println!("Generated the node config {}", rollup_config.display());
+            for name in network.keys.keys() {
+                println!(
+                    "Generated a key for account `{name}` in {}",
+                    keys_dir.display()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,31 +1,33 @@
 //! This binary runs the rollup full node.
 
-use anyhow::Context;
-use clap::Parser;
-#[cfg(feature = "celestia_da")]
-use sov_celestia_adapter::CelestiaService;
-#[cfg(feature = "mock_da")]
-use sov_mock_da::storable::service::StorableMockDaService;
-use sov_modules_rollup_blueprint::FullNodeBlueprint;
+use anyhow::{bail, Context};
+use clap::{Parser, Subcommand};
+use sov_db::storage_manager::HierarchicalStorageManager;
+use sov_modules_api::{ApiStateAccessor, Spec};
 use sov_modules_rollup_blueprint::Rollup;
+use sov_modules_rollup_blueprint::{FullNodeBlueprint, RollupBlueprint};
 use sov_rollup_interface::execution_mode::Native;
-use sov_rollup_interface::node::da::DaServiceWithRetries;
 #[cfg(feature = "celestia_da")]
 use sov_rollup_starter::celestia_rollup::CelestiaRollup;
+use sov_rollup_starter::config_overrides::{collect_overrides, redact_secrets, ConfigOverride};
 #[cfg(feature = "mock_da")]
 use sov_rollup_starter::mock_rollup::MockRollup;
-use sov_stf_runner::processes::RollupProverConfig;
-use sov_stf_runner::{from_toml_path, RollupConfig};
+use sov_rollup_starter::prover_mode::{
+    read_rollup_config, ProverMode, ProverSection, PROVER_MODE_ENV_VAR,
+};
+use sov_rollup_starter::soft_confirmations::check_sequencer_settings;
+use sov_stf_runner::RollupConfig;
 use std::env;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use stf_starter::genesis_config::GenesisPaths;
+use stf_starter::genesis_config::{create_genesis_config, GenesisDocument, GenesisSource};
+use stf_starter::genesis_validation::validate_genesis;
+use stf_starter::state_export::{check_total_supply, export_state};
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{fmt, EnvFilter};
 
-use sha2::Sha256;
-use sov_modules_api::Address;
-
 #[cfg(all(feature = "mock_da", feature = "celestia_da"))]
 compile_error!("Both mock_da and celestia_da are enabled, but only one should be.");
 
@@ -44,24 +46,104 @@ const DEFAULT_CONFIG_PATH: &str = "../../celestia_rollup_config.toml";
 #[cfg(all(feature = "celestia_da", not(feature = "mock_da")))]
 const DEFAULT_GENESIS_PATH: &str = "../../test-data/genesis/celestia/";
 
+#[cfg(all(feature = "celestia_da", not(feature = "mock_da")))]
+type StarterRollup = CelestiaRollup<Native>;
+#[cfg(all(feature = "mock_da", not(feature = "celestia_da")))]
+type StarterRollup = MockRollup<Native>;
+
+type StarterSpec = <StarterRollup as RollupBlueprint<Native>>::Spec;
+
+type StarterRollupConfig = RollupConfig<
+    <StarterSpec as Spec>::Address,
+    <StarterRollup as FullNodeBlueprint<Native>>::DaService,
+>;
+
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    /// Runs the rollup if omitted.
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The path to the rollup config.
-    #[arg(long, default_value = DEFAULT_CONFIG_PATH)]
+    #[arg(long, global = true, default_value = DEFAULT_CONFIG_PATH)]
     rollup_config_path: String,
 
-    /// The path to the genesis config.
-    #[arg(long, default_value = DEFAULT_GENESIS_PATH)]
+    /// The path to the genesis config: a directory with one file per module, or a single
+    /// JSON or TOML file.
+    #[arg(long, global = true, default_value = DEFAULT_GENESIS_PATH)]
     genesis_paths: String,
 
+    /// Overrides a field of the rollup config, e.g. `--set runner.rpc_config.bind_port=12346`.
+    /// Takes precedence over the `TMC_`-prefixed environment variables, such as
+    /// `TMC_RUNNER__RPC_CONFIG__BIND_PORT`.
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE")]
+    overrides: Vec<ConfigOverride>,
+
+    /// The prover mode: none, skip, simulate, execute or prove. Overrides the `[prover]`
+    /// section of the rollup config and the `SOV_PROVER_MODE` environment variable.
+    #[arg(long, global = true)]
+    prover_mode: Option<String>,
+
     /// The optional path to the log file.
-    #[arg(long, default_value = None)]
+    #[arg(long, global = true, default_value = None)]
     log_dir: Option<String>,
 
-    /// The optional path to the log file.
-    #[arg(long, default_value_t = 9845)]
-    metrics: u64,
+    /// The address the Prometheus metrics are served on.
+    #[arg(long, global = true, default_value = "127.0.0.1")]
+    metrics_host: IpAddr,
+
+    /// The port the Prometheus metrics are served on.
+    #[arg(long, global = true, default_value_t = 9845)]
+    metrics: u16,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Runs the rollup.
+    Run,
+    /// Creates the data directory, and writes a rollup config and a genesis to
+    /// `--rollup-config-path` and `--genesis-paths` from templates.
+    Init {
+        /// The data directory, replacing `storage.path` of the template config.
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// The rollup config to start from.
+        #[arg(long, default_value = DEFAULT_CONFIG_PATH)]
+        template_config: PathBuf,
+        /// The genesis to start from, a directory or a single file.
+        #[arg(long, default_value = DEFAULT_GENESIS_PATH)]
+        template_genesis: PathBuf,
+        /// Overwrites the existing config and genesis.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Checks the rollup config, the prover mode and the genesis without starting anything.
+    ValidateConfig,
+    /// Prints the rollup config with every default filled in and the resolved prover mode.
+    /// Credentials such as `da.celestia_rpc_auth_token` are redacted.
+    PrintConfig,
+    /// Exports the state at a height into a new genesis directory, for a coordinated restart.
+    ///
+    /// Reads the storage of the node configured by `--rollup-config-path`, which must be
+    /// stopped. The genesis the chain was started from, `--genesis-paths`, provides the
+    /// settings that are not exported, see `stf_starter::state_export`. Fails if the exported
+    /// balances of a token do not add up to its total supply.
+    ///
+    /// The credentials of the `accounts` module are copied from `--genesis-paths`: accounts
+    /// that registered a credential after genesis must register it again.
+    ExportState {
+        /// The rollup height to export.
+        #[arg(long)]
+        height: u64,
+        /// The directory to write the genesis files to.
+        #[arg(long)]
+        output_dir: PathBuf,
+        /// A JSON file with the addresses that are not in the original genesis but whose
+        /// balances must be kept.
+        #[arg(long)]
+        addresses: Option<PathBuf>,
+    },
 }
 
 fn init_logging(log_dir: Option<String>) -> Option<WorkerGuard> {
@@ -93,92 +175,304 @@ fn init_logging(log_dir: Option<String>) -> Option<WorkerGuard> {
 async fn main() {
     let args = Args::parse();
 
-    let guard = init_logging(args.log_dir);
+    let guard = init_logging(args.log_dir.clone());
     let prev_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
         tracing_panic::panic_hook(panic_info);
         prev_hook(panic_info);
     }));
 
-    let metrics_port = args.metrics;
-    let address = format!("127.0.0.1:{}", metrics_port);
-    prometheus_exporter::start(address.parse().unwrap())
-        .expect("Could not start prometheus server");
+    let rollup_config_path = Path::new(&args.rollup_config_path);
+    let genesis_paths = Path::new(&args.genesis_paths);
+    let prover_mode = args.prover_mode.as_deref();
+    let overrides = collect_overrides(env::vars(), &args.overrides);
+    let overrides = overrides.as_slice();
 
-    let rollup_config_path = args.rollup_config_path.as_str();
+    let result = match args.command.unwrap_or(Command::Run) {
+        Command::Run => {
+            run(
+                rollup_config_path,
+                overrides,
+                genesis_paths,
+                prover_mode,
+                SocketAddr::new(args.metrics_host, args.metrics),
+            )
+            .await;
+            Ok(())
+        }
+        Command::Init {
+            data_dir,
+            template_config,
+            template_genesis,
+            force,
+        } => init(
+            rollup_config_path,
+            overrides,
+            genesis_paths,
+            data_dir.as_deref(),
+            &template_config,
+            &template_genesis,
+            force,
+        )
+        .and_then(|()| validate_config(rollup_config_path, overrides, genesis_paths, prover_mode)),
+        Command::ValidateConfig => {
+            validate_config(rollup_config_path, overrides, genesis_paths, prover_mode)
+        }
+        Command::PrintConfig => print_config(rollup_config_path, overrides, prover_mode),
+        Command::ExportState {
+            height,
+            output_dir,
+            addresses,
+        } => export_state_at(
+            rollup_config_path,
+            overrides,
+            genesis_paths,
+            height,
+            &output_dir,
+            addresses.as_deref(),
+        ),
+    };
 
-    let genesis_paths = args.genesis_paths.as_str();
+    if let Err(error) = &result {
+        tracing::error!("{error:?}");
+    }
+    drop(guard);
+    if result.is_err() {
+        std::process::exit(1);
+    }
+}
+
+async fn run(
+    rollup_config_path: &Path,
+    overrides: &[ConfigOverride],
+    genesis_paths: &Path,
+    prover_mode: Option<&str>,
+    metrics_address: SocketAddr,
+) {
+    prometheus_exporter::start(metrics_address).expect("Could not start prometheus server");
+    tracing::info!(%metrics_address, "Serving Prometheus metrics");
 
-    let prover_config = parse_prover_config().expect("Malformed prover_config");
-    tracing::info!(?prover_config, "Running demo rollup with prover config");
+    tracing::info!(
+        soft_confirmations = stf_starter::SOFT_CONFIRMATIONS,
+        "Running demo rollup with kernel"
+    );
 
     let rollup = new_rollup(
-        &GenesisPaths::from_dir(genesis_paths),
+        &GenesisSource::from_path(genesis_paths),
         rollup_config_path,
-        prover_config,
+        overrides,
+        prover_mode,
     )
     .await
     .expect("Couldn't start rollup");
     rollup.run().await.expect("Couldn't run rollup");
-    drop(guard);
 }
 
-fn parse_prover_config() -> anyhow::Result<Option<RollupProverConfig>> {
-    if let Some(value) = option_env!("SOV_PROVER_MODE") {
-        let config = std::str::FromStr::from_str(value).map_err(|error| {
-            tracing::error!(value, ?error, "Unknown `SOV_PROVER_MODE` value; aborting");
-            error
-        })?;
-        Ok(Some(config))
+/// Reads the rollup config with the overrides applied, checking the settings that depend on
+/// how the rollup was built.
+fn load_config(
+    rollup_config_path: &Path,
+    overrides: &[ConfigOverride],
+) -> anyhow::Result<(StarterRollupConfig, ProverSection)> {
+    for config_override in overrides {
+        // Values are not logged, they may hold credentials.
+        tracing::info!(
+            key = config_override.key,
+            source = %config_override.source,
+            "Overriding a rollup config field"
+        );
+    }
+    let (rollup_config, prover_section): (StarterRollupConfig, _) =
+        read_rollup_config(rollup_config_path, overrides)?;
+    check_sequencer_settings(rollup_config.sequencer.max_allowed_blocks_behind)?;
+    Ok((rollup_config, prover_section))
+}
+
+fn init(
+    rollup_config_path: &Path,
+    overrides: &[ConfigOverride],
+    genesis_paths: &Path,
+    data_dir: Option<&Path>,
+    template_config: &Path,
+    template_genesis: &Path,
+    force: bool,
+) -> anyhow::Result<()> {
+    for path in [rollup_config_path, genesis_paths] {
+        if path.exists() && !force {
+            bail!(
+                "{} already exists, pass `--force` to overwrite it",
+                path.display()
+            );
+        }
+    }
+
+    let template = std::fs::read_to_string(template_config).with_context(|| {
+        format!(
+            "Failed to read rollup configuration from {}",
+            template_config.display()
+        )
+    })?;
+    let rollup_config = match data_dir {
+        // Only rewrite the template when needed, to keep its comments.
+        None => template,
+        Some(data_dir) => {
+            let mut config: toml::Table = toml::from_str(&template).with_context(|| {
+                format!("Invalid rollup configuration {}", template_config.display())
+            })?;
+            let storage = config
+                .entry("storage")
+                .or_insert_with(|| toml::Table::new().into());
+            let Some(storage) = storage.as_table_mut() else {
+                bail!("`storage` must be a table in {}", template_config.display());
+            };
+            let data_dir = data_dir
+                .to_str()
+                .context("The data directory is not UTF-8")?;
+            storage.insert("path".to_string(), data_dir.into());
+            toml::to_string_pretty(&config)?
+        }
+    };
+    if let Some(parent) = rollup_config_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(rollup_config_path, rollup_config)
+        .with_context(|| format!("Failed to write {}", rollup_config_path.display()))?;
+
+    let (rollup_config, _) = load_config(rollup_config_path, overrides)?;
+    std::fs::create_dir_all(&rollup_config.storage.path).with_context(|| {
+        format!(
+            "Failed to create the data directory {}",
+            rollup_config.storage.path.display()
+        )
+    })?;
+
+    let genesis = GenesisDocument::read(&GenesisSource::from_path(template_genesis))?;
+    let is_file = genesis_paths
+        .extension()
+        .is_some_and(|extension| extension == "json" || extension == "toml");
+    if is_file {
+        genesis.write_file(genesis_paths)?;
     } else {
-        Ok(None)
+        genesis.write_dir(genesis_paths)?;
     }
+
+    tracing::info!(
+        rollup_config = %rollup_config_path.display(),
+        genesis = %genesis_paths.display(),
+        data_dir = %rollup_config.storage.path.display(),
+        "Initialized the node"
+    );
+    Ok(())
 }
 
-#[cfg(all(feature = "mock_da", not(feature = "celestia_da")))]
-async fn new_rollup(
-    rt_genesis_paths: &GenesisPaths,
-    rollup_config_path: &str,
-    prover_config: Option<RollupProverConfig>,
-) -> Result<Rollup<MockRollup<Native>, Native>, anyhow::Error> {
-    tracing::info!("Starting mock rollup with config {}", rollup_config_path);
-
-    let rollup_config: RollupConfig<Address<Sha256>, DaServiceWithRetries<StorableMockDaService>> =
-        from_toml_path(rollup_config_path).with_context(|| {
-            format!(
-                "Failed to read rollup configuration from {}",
-                rollup_config_path
-            )
-        })?;
+fn validate_config(
+    rollup_config_path: &Path,
+    overrides: &[ConfigOverride],
+    genesis_paths: &Path,
+    prover_mode: Option<&str>,
+) -> anyhow::Result<()> {
+    let (_, prover_section) = load_config(rollup_config_path, overrides)?;
+    let prover_mode = ProverMode::from_env(prover_mode, &prover_section)?;
+    create_genesis_config::<StarterSpec>(&GenesisSource::from_path(genesis_paths))?;
 
-    let mock_rollup = MockRollup::default();
+    println!(
+        "The rollup config {} and the genesis {} are valid, prover mode `{}` from {}",
+        rollup_config_path.display(),
+        genesis_paths.display(),
+        prover_mode.mode,
+        prover_mode.source
+    );
+    Ok(())
+}
 
-    mock_rollup
-        .create_new_rollup(rt_genesis_paths, rollup_config, prover_config)
-        .await
+fn print_config(
+    rollup_config_path: &Path,
+    overrides: &[ConfigOverride],
+    prover_mode: Option<&str>,
+) -> anyhow::Result<()> {
+    let (rollup_config, prover_section) = load_config(rollup_config_path, overrides)?;
+    let prover_mode = ProverMode::from_env(prover_mode, &prover_section)?;
+
+    let mut config = toml::Table::try_from(&rollup_config)?;
+    redact_secrets(&mut config);
+    config.insert(
+        "prover".to_string(),
+        toml::Value::try_from(ProverSection {
+            mode: Some(prover_mode.mode),
+        })?,
+    );
+
+    println!(
+        "# Resolved from {}, prover mode from {}",
+        rollup_config_path.display(),
+        prover_mode.source
+    );
+    for config_override in overrides {
+        println!(
+            "# `{}` overridden by {}",
+            config_override.key, config_override.source
+        );
+    }
+    println!("{}", toml::to_string_pretty(&config)?);
+    Ok(())
+}
+
+fn export_state_at(
+    rollup_config_path: &Path,
+    overrides: &[ConfigOverride],
+    genesis_paths: &Path,
+    height: u64,
+    output_dir: &Path,
+    addresses: Option<&Path>,
+) -> anyhow::Result<()> {
+    let (rollup_config, _) = load_config(rollup_config_path, overrides)?;
+    let addresses: Vec<<StarterSpec as Spec>::Address> = match addresses {
+        Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)
+            .with_context(|| format!("Invalid address list {}", path.display()))?,
+        None => Vec::new(),
+    };
+    let base = GenesisDocument::read(&GenesisSource::from_path(genesis_paths))?;
+
+    let mut storage_manager = StarterRollup::default().create_storage_manager(&rollup_config)?;
+    let storage = storage_manager.create_finalized_storage()?;
+    let mut state = ApiStateAccessor::<StarterSpec>::new(storage).get_archival_at(height)?;
+
+    tracing::info!(
+        height,
+        storage = %rollup_config.storage.path.display(),
+        "Exporting the rollup state"
+    );
+    let genesis = export_state::<StarterSpec>(&base, &addresses, &mut state)?;
+    check_total_supply::<StarterSpec>(&genesis, &mut state)?;
+    genesis.write_dir(output_dir)?;
+    validate_genesis::<StarterSpec>(&GenesisSource::from_path(output_dir))?;
+    tracing::info!(output_dir = %output_dir.display(), "Exported the rollup state");
+
+    Ok(())
 }
 
-#[cfg(all(feature = "celestia_da", not(feature = "mock_da")))]
 async fn new_rollup(
-    rt_genesis_paths: &GenesisPaths,
-    rollup_config_path: &str,
-    prover_config: Option<RollupProverConfig>,
-) -> Result<Rollup<CelestiaRollup<Native>, Native>, anyhow::Error> {
+    rt_genesis_paths: &GenesisSource,
+    rollup_config_path: &Path,
+    overrides: &[ConfigOverride],
+    prover_mode: Option<&str>,
+) -> Result<Rollup<StarterRollup, Native>, anyhow::Error> {
     tracing::info!(
-        "Starting Celestia rollup with config {}",
-        rollup_config_path
+        "Starting rollup with config {}",
+        rollup_config_path.display()
     );
 
-    let rollup_config: RollupConfig<Address<Sha256>, DaServiceWithRetries<CelestiaService>> =
-        from_toml_path(rollup_config_path).with_context(|| {
-            format!(
-                "Failed to read rollup configuration from {}",
-                rollup_config_path
-            )
-        })?;
+    let (rollup_config, prover_section) = load_config(rollup_config_path, overrides)?;
+    let prover_mode = ProverMode::from_env(prover_mode, &prover_section)?;
+    tracing::info!(
+        prover_config = ?prover_mode.config,
+        mode = prover_mode.mode,
+        source = %prover_mode.source,
+        env_var = PROVER_MODE_ENV_VAR,
+        "Running demo rollup with prover mode"
+    );
 
-    let celestia_rollup = CelestiaRollup::default();
-    celestia_rollup
-        .create_new_rollup(rt_genesis_paths, rollup_config, prover_config)
+    StarterRollup::default()
+        .create_new_rollup(rt_genesis_paths, rollup_config, prover_mode.config)
         .await
 }
@@ -122,6 +122,8 @@ impl FullNodeBlueprint<Native> for CelestiaRollup<Native> {
         da_sync_state: Arc<DaSyncState>,
         rollup_config: &RollupConfig<<Self::Spec as Spec>::Address, Self::DaService>,
     ) -> anyhow::Result<RuntimeEndpoints> {
+        crate::metrics::spawn_node_metrics(sync_status_receiver.clone(), ledger_db);
+
         sov_modules_rollup_blueprint::register_endpoints::<Self, _>(
             storage.clone(),
             sync_status_receiver,
@@ -0,0 +1,224 @@
+//! Layered rollup configuration.
+//!
+//! Any field of `rollup_config.toml` can be overridden without editing the file, addressed by
+//! its dotted path, e.g. `runner.rpc_config.bind_port`. From lowest to highest precedence:
+//! 1. the rollup config file,
+//! 2. `TMC_`-prefixed environment variables, with `__` between the path segments:
+//!    `TMC_RUNNER__RPC_CONFIG__BIND_PORT=12346`,
+//! 3. the `--set runner.rpc_config.bind_port=12346` flags of the node, the last one winning.
+//!
+//! Values are typed from the field they replace: `12346` overriding an integer is parsed as
+//! one, while the same value overriding a string, like `da.sender_address`, stays a string.
+//! Only the fields of the config can be overridden, whether set in the file or filled in by a
+//! default. Optional fields without a default must be set in the file first.
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+
+/// The prefix of the environment variables overriding the rollup config.
+pub const CONFIG_ENV_PREFIX: &str = "TMC_";
+
+/// The fields of the rollup config holding credentials, hidden by [`redact_secrets`].
+pub const SECRET_FIELDS: &[&str] = &["da.celestia_rpc_auth_token"];
+
+/// The value of the fields hidden by [`redact_secrets`].
+pub const REDACTED: &str = "<redacted>";
+
+/// Separates the path segments in the name of an environment variable, since field names
+/// contain single underscores.
+const ENV_PATH_SEPARATOR: &str = "__";
+
+/// Where an override comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverrideSource {
+    /// The environment variable of this name.
+    Env(String),
+    /// A `--set` flag.
+    Flag,
+}
+
+impl fmt::Display for OverrideSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverrideSource::Env(name) => write!(f, "{name}"),
+            OverrideSource::Flag => write!(f, "--set"),
+        }
+    }
+}
+
+/// A value replacing the field at a dotted path of the rollup config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigOverride {
+    /// The path of the field, e.g. `runner.rpc_config.bind_port`.
+    pub key: String,
+    /// The new value, before parsing.
+    pub value: String,
+    /// Where the override comes from.
+    pub source: OverrideSource,
+}
+
+impl ConfigOverride {
+    /// The override set by an environment variable, `None` if the variable doesn't start
+    /// with [`CONFIG_ENV_PREFIX`].
+    pub fn from_env_var(name: &str, value: &str) -> Option<Self> {
+        let path = name.strip_prefix(CONFIG_ENV_PREFIX)?;
+        let key = path
+            .split(ENV_PATH_SEPARATOR)
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(".");
+
+        Some(Self {
+            key,
+            value: value.to_string(),
+            source: OverrideSource::Env(name.to_string()),
+        })
+    }
+}
+
+/// Parses the `key=value` argument of `--set`.
+impl FromStr for ConfigOverride {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, value) = s
+            .split_once('=')
+            .with_context(|| format!("Expected `key=value`, got `{s}`"))?;
+
+        Ok(Self {
+            key: key.trim().to_string(),
+            value: value.to_string(),
+            source: OverrideSource::Flag,
+        })
+    }
+}
+
+/// Orders the overrides from the environment and the `--set` flags by precedence, the last
+/// one winning.
+pub fn collect_overrides(
+    env: impl IntoIterator<Item = (String, String)>,
+    flags: &[ConfigOverride],
+) -> Vec<ConfigOverride> {
+    let mut overrides: Vec<_> = env
+        .into_iter()
+        .filter_map(|(name, value)| ConfigOverride::from_env_var(&name, &value))
+        .collect();
+    // The order of the environment is unspecified, sort it to be deterministic.
+    overrides.sort_by(|a, b| a.key.cmp(&b.key));
+    overrides.extend(flags.iter().cloned());
+    overrides
+}
+
+/// Applies `overrides` to the rollup config, in order, creating the missing tables.
+///
+/// `known` holds every field that may be overridden, with a value of its type, see
+/// [`crate::prover_mode::read_rollup_config`]. Overrides of other fields are rejected when
+/// they come from a `--set` flag, and ignored with a warning when they come from the
+/// environment, which may hold unrelated `TMC_` variables.
+pub fn apply_overrides(
+    config: &mut toml::Table,
+    known: &toml::Table,
+    overrides: &[ConfigOverride],
+) -> anyhow::Result<()> {
+    let mut unknown = Vec::new();
+    for config_override in overrides {
+        let segments: Vec<&str> = config_override.key.split('.').collect();
+        let Some(field) = known_field(known, &segments) else {
+            match config_override.source {
+                OverrideSource::Env(_) => tracing::warn!(
+                    key = config_override.key,
+                    source = %config_override.source,
+                    "Ignoring an override of an unknown rollup config field"
+                ),
+                OverrideSource::Flag => unknown.push(format!(
+                    "`{}` from {}",
+                    config_override.key, config_override.source
+                )),
+            }
+            continue;
+        };
+
+        apply_override(config, &segments, field, &config_override.value).with_context(|| {
+            format!(
+                "Invalid override of `{}` from {}",
+                config_override.key, config_override.source
+            )
+        })?;
+    }
+
+    if !unknown.is_empty() {
+        bail!("Unknown rollup config fields: {}", unknown.join(", "));
+    }
+    Ok(())
+}
+
+/// The value of the field at `segments` in `known`, `None` if there is no such field.
+fn known_field<'a>(known: &'a toml::Table, segments: &[&str]) -> Option<&'a toml::Value> {
+    let (field, tables) = segments.split_last()?;
+    let mut table = known;
+    for segment in tables {
+        table = table.get(*segment)?.as_table()?;
+    }
+    table.get(*field)
+}
+
+fn apply_override(
+    config: &mut toml::Table,
+    segments: &[&str],
+    known: &toml::Value,
+    value: &str,
+) -> anyhow::Result<()> {
+    let (field, tables) = segments
+        .split_last()
+        .expect("split always returns a segment");
+
+    let mut table = config;
+    for segment in tables {
+        table = table
+            .entry(*segment)
+            .or_insert_with(|| toml::Table::new().into())
+            .as_table_mut()
+            .with_context(|| format!("`{segment}` is not a table"))?;
+    }
+
+    table.insert(field.to_string(), parse_value(known, value)?);
+    Ok(())
+}
+
+/// Parses `value` as the type of `known`: strings are taken as they are, everything else is
+/// parsed as TOML.
+fn parse_value(known: &toml::Value, value: &str) -> anyhow::Result<toml::Value> {
+    if known.is_str() {
+        return Ok(value.into());
+    }
+
+    let parsed = toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"));
+    match parsed {
+        Some(toml::Value::Integer(integer)) if known.is_float() => Ok((integer as f64).into()),
+        Some(parsed) if parsed.same_type(known) => Ok(parsed),
+        _ => bail!("Expected {}, got `{value}`", known.type_str()),
+    }
+}
+
+/// Hides the value of every [`SECRET_FIELDS`] set in `config`, before printing it.
+pub fn redact_secrets(config: &mut toml::Table) {
+    for key in SECRET_FIELDS {
+        let segments: Vec<&str> = key.split('.').collect();
+        let (field, tables) = segments
+            .split_last()
+            .expect("split always returns a segment");
+
+        let mut table = Some(&mut *config);
+        for segment in tables {
+            table = table
+                .and_then(|table| table.get_mut(*segment))
+                .and_then(toml::Value::as_table_mut);
+        }
+        if let Some(value) = table.and_then(|table| table.get_mut(*field)) {
+            *value = REDACTED.into();
+        }
+    }
+}
@@ -0,0 +1,439 @@
+//! Generation of the genesis of a new network.
+//!
+//! A network is described by a [`NetworkSpec`], usually read from a TOML file:
+//!
+//! ```toml
+//! governor = "operator"
+//!
+//! [[accounts]]
+//! name = "operator"
+//! balance = 1_000_000_000
+//! # Omit both `key_file` and `address` to generate a new key.
+//! # key_file = "test-data/keys/token_deployer_private_key.json"
+//! # address = "sov1..."
+//!
+//! [sequencer]
+//! account = "operator"
+//! da_address = "0000000000000000000000000000000000000000000000000000000000000000"
+//! bond = 10_000_000
+//!
+//! [[attesters]]
+//! account = "operator"
+//! bond = 200_000
+//!
+//! [[provers]]
+//! account = "operator"
+//! bond = 200_000
+//!
+//! [chain]
+//! evm_chain_id = 4321
+//! genesis_da_height = 0
+//! ```
+//!
+//! The parameters the spec does not cover, like the minimum bonds or the EVM settings, are
+//! taken from a base genesis, and the node settings from a base `rollup_config.toml`.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sov_cli::wallet_state::PrivateKeyAndAddress;
+use sov_modules_api::{CryptoSpec, PrivateKey, PublicKey, Spec};
+use stf_starter::genesis_config::{GenesisDocument, GenesisPaths, GenesisSource};
+use stf_starter::genesis_validation::validate_genesis;
+
+type Hasher<S> = <<S as Spec>::CryptoSpec as CryptoSpec>::Hasher;
+
+/// Description of a new network.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkSpec {
+    /// Name of the gas token, defaults to the one of the base genesis.
+    #[serde(default)]
+    pub token_name: Option<String>,
+    /// The account governing the rollup, which also owns the example module and mints the gas
+    /// token. Defaults to the sequencer account.
+    #[serde(default)]
+    pub governor: Option<String>,
+    /// The accounts holding the gas token at genesis.
+    pub accounts: Vec<AccountSpec>,
+    /// The preferred sequencer.
+    pub sequencer: SequencerSpec,
+    /// The initial attesters.
+    #[serde(default)]
+    pub attesters: Vec<BondSpec>,
+    /// The initial provers.
+    #[serde(default)]
+    pub provers: Vec<BondSpec>,
+    /// Chain parameters.
+    #[serde(default)]
+    pub chain: ChainSpec,
+}
+
+/// An account of the network, referred to by its name in the rest of the spec.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountSpec {
+    /// The name of the account, also used to name its generated key file.
+    pub name: String,
+    /// The gas token balance of the account.
+    pub balance: u64,
+    /// A key file of the account, in the format of `test-data/keys`.
+    #[serde(default)]
+    pub key_file: Option<PathBuf>,
+    /// The address of an account whose key is kept elsewhere.
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+/// The preferred sequencer.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SequencerSpec {
+    /// The account of the sequencer.
+    pub account: String,
+    /// The DA address the sequencer posts batches from.
+    pub da_address: String,
+    /// The bond of the sequencer, in gas tokens.
+    pub bond: u64,
+}
+
+/// An initial attester or prover.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BondSpec {
+    /// The bonded account.
+    pub account: String,
+    /// The bond, in gas tokens.
+    pub bond: u64,
+}
+
+/// Chain parameters, all optional: the base genesis provides the defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainSpec {
+    /// The chain id of the EVM module.
+    #[serde(default)]
+    pub evm_chain_id: Option<u64>,
+    /// The DA height the rollup starts at, also used as `runner.genesis_height`.
+    #[serde(default)]
+    pub genesis_da_height: Option<u64>,
+    /// The number of slots after which attestations are final.
+    #[serde(default)]
+    pub rollup_finality_period: Option<u64>,
+}
+
+/// The genesis, node config and keys of a new network.
+pub struct GeneratedNetwork<S: Spec> {
+    /// The genesis of every module.
+    pub genesis: GenesisDocument,
+    /// The `rollup_config.toml` of the sequencer node.
+    pub rollup_config: toml::Table,
+    /// The keys generated for the accounts without a key file or address, by account name.
+    pub keys: BTreeMap<String, PrivateKeyAndAddress<S>>,
+}
+
+impl NetworkSpec {
+    /// Reads a spec from a TOML file.
+    pub fn from_toml_path(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read network spec {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Invalid network spec {}", path.display()))
+    }
+
+    /// Generates the network on top of the genesis read from `base_genesis` and the node config
+    /// in `base_rollup_config`.
+    pub fn generate<S: Spec>(
+        &self,
+        base_genesis: &GenesisSource,
+        base_rollup_config: &Path,
+    ) -> anyhow::Result<GeneratedNetwork<S>> {
+        let mut keys = BTreeMap::new();
+        let mut addresses = BTreeMap::new();
+        let mut accounts = Vec::new();
+
+        for account in &self.accounts {
+            let (address, credential_id) = match (&account.key_file, &account.address) {
+                (Some(_), Some(_)) => bail!(
+                    "Account `{}` sets both `key_file` and `address`",
+                    account.name
+                ),
+                (Some(key_file), None) => {
+                    let key = read_key::<S>(key_file)?;
+                    (json!(key.address), Some(credential_id::<S>(&key)))
+                }
+                (None, Some(address)) => (json!(address), None),
+                (None, None) => {
+                    let private_key = <S::CryptoSpec as CryptoSpec>::PrivateKey::generate();
+                    let credential_id = private_key.pub_key().credential_id::<Hasher<S>>();
+                    let key = PrivateKeyAndAddress::<S> {
+                        address: credential_id.into(),
+                        private_key,
+                    };
+                    let address = json!(key.address);
+                    keys.insert(account.name.clone(), key);
+                    (address, Some(json!(credential_id)))
+                }
+            };
+
+            if let Some(credential_id) = credential_id {
+                accounts.push(json!({ "credential_id": credential_id, "address": address }));
+            }
+            if addresses.insert(account.name.as_str(), address).is_some() {
+                bail!("Account `{}` is listed twice", account.name);
+            }
+        }
+
+        let address_of = |name: &str| {
+            addresses
+                .get(name)
+                .cloned()
+                .with_context(|| format!("Unknown account `{name}`"))
+        };
+        let bonds = |bonds: &[BondSpec]| {
+            bonds
+                .iter()
+                .map(|bond| Ok(json!([address_of(&bond.account)?, bond.bond])))
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+
+        let sequencer = address_of(&self.sequencer.account)?;
+        let governor = match &self.governor {
+            Some(governor) => address_of(governor)?,
+            None => sequencer.clone(),
+        };
+
+        let mut genesis = GenesisDocument::read(base_genesis)?;
+
+        module(&mut genesis, "accounts")?["accounts"] = json!(accounts);
+
+        let bank = module(&mut genesis, "bank")?;
+        bank["gas_token_config"]["address_and_balances"] = self
+            .accounts
+            .iter()
+            .map(|account| json!([addresses[account.name.as_str()], account.balance]))
+            .collect();
+        bank["gas_token_config"]["authorized_minters"] = json!([governor]);
+        if let Some(token_name) = &self.token_name {
+            bank["gas_token_config"]["token_name"] = json!(token_name);
+        }
+        bank["tokens"] = json!([]);
+
+        let sequencer_registry = module(&mut genesis, "sequencer_registry")?;
+        sequencer_registry["seq_rollup_address"] = sequencer.clone();
+        sequencer_registry["seq_da_address"] = json!(self.sequencer.da_address);
+        sequencer_registry["seq_bond"] = json!(self.sequencer.bond);
+
+        let attester_incentives = module(&mut genesis, "attester_incentives")?;
+        attester_incentives["initial_attesters"] = json!(bonds(&self.attesters)?);
+        if let Some(period) = self.chain.rollup_finality_period {
+            attester_incentives["rollup_finality_period"] = json!(period);
+        }
+
+        module(&mut genesis, "prover_incentives")?["initial_provers"] =
+            json!(bonds(&self.provers)?);
+
+        let example_module = module(&mut genesis, "example_module")?;
+        example_module["owner"] = governor.clone();
+        example_module["writers"] = json!([]);
+
+        module(&mut genesis, "governance")?["governor"] = governor;
+        module(&mut genesis, "paymaster")?["sponsors"] = json!([]);
+
+        // A new network starts without EVM accounts, they cannot be derived from the spec.
+        let evm = module(&mut genesis, "evm")?;
+        evm["data"] = json!([]);
+        if let Some(chain_id) = self.chain.evm_chain_id {
+            evm["chain_id"] = json!(chain_id);
+        }
+
+        if let Some(height) = self.chain.genesis_da_height {
+            module(&mut genesis, "chain_state")?["genesis_da_height"] = json!(height);
+        }
+
+        let rollup_config = self.rollup_config(base_rollup_config, &addresses)?;
+
+        Ok(GeneratedNetwork {
+            genesis,
+            rollup_config,
+            keys,
+        })
+    }
+
+    /// The base node config, set up to run the sequencer of the network.
+    fn rollup_config(
+        &self,
+        base_rollup_config: &Path,
+        addresses: &BTreeMap<&str, Value>,
+    ) -> anyhow::Result<toml::Table> {
+        let contents = std::fs::read_to_string(base_rollup_config).with_context(|| {
+            format!(
+                "Failed to read rollup configuration from {}",
+                base_rollup_config.display()
+            )
+        })?;
+        let mut config: toml::Table = toml::from_str(&contents).with_context(|| {
+            format!(
+                "Invalid rollup configuration {}",
+                base_rollup_config.display()
+            )
+        })?;
+
+        let da_address = toml::Value::from(self.sequencer.da_address.as_str());
+        if let Some(da) = table(&mut config, "da") {
+            // The mock DA and Celestia name the address of the node differently.
+            for key in ["sender_address", "own_celestia_address"] {
+                if da.contains_key(key) {
+                    da.insert(key.to_string(), da_address.clone());
+                }
+            }
+        }
+        if let Some(sequencer) = table(&mut config, "sequencer") {
+            sequencer.insert("da_address".to_string(), da_address);
+        }
+
+        if let Some(height) = self.chain.genesis_da_height {
+            if let Some(runner) = table(&mut config, "runner") {
+                runner.insert(
+                    "genesis_height".to_string(),
+                    toml::Value::from(i64::try_from(height)?),
+                );
+            }
+        }
+
+        if let (Some(prover), Some(proof_manager)) =
+            (self.provers.first(), table(&mut config, "proof_manager"))
+        {
+            if let Some(Value::String(address)) = addresses.get(prover.account.as_str()) {
+                proof_manager.insert(
+                    "prover_address".to_string(),
+                    toml::Value::from(address.as_str()),
+                );
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl<S: Spec> GeneratedNetwork<S> {
+    /// Validates the genesis, then writes it to `genesis_dir`, the node config to
+    /// `rollup_config_path` and the generated keys to `<keys_dir>/<account>_private_key.json`.
+    ///
+    /// The genesis is validated in a staging directory next to `genesis_dir` and only then
+    /// moved into place, so an invalid genesis leaves nothing behind. Existing genesis files,
+    /// node config and key files are only overwritten if `force` is set.
+    pub fn write(
+        &self,
+        genesis_dir: &Path,
+        rollup_config_path: &Path,
+        keys_dir: &Path,
+        force: bool,
+    ) -> anyhow::Result<()> {
+        if !force {
+            let genesis_files = self
+                .genesis
+                .modules
+                .keys()
+                .map(|module| genesis_dir.join(format!("{module}.json")));
+            let key_files = self
+                .keys
+                .keys()
+                .map(|name| keys_dir.join(format!("{name}_private_key.json")));
+            for path in genesis_files
+                .chain([rollup_config_path.to_path_buf()])
+                .chain(key_files)
+            {
+                if path.exists() {
+                    bail!(
+                        "{} already exists, pass `--force` to overwrite it",
+                        path.display()
+                    );
+                }
+            }
+        }
+
+        let parent = match genesis_dir.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+        let staging = tempfile::Builder::new()
+            .prefix(".genesis-")
+            .tempdir_in(parent)
+            .with_context(|| {
+                format!(
+                    "Failed to create a staging directory in {}",
+                    parent.display()
+                )
+            })?;
+
+        self.genesis.write_dir(staging.path())?;
+        validate_genesis::<S>(&GenesisSource::Dir(GenesisPaths::from_dir(staging.path())))?;
+
+        std::fs::create_dir_all(genesis_dir)
+            .with_context(|| format!("Failed to create {}", genesis_dir.display()))?;
+        for module in self.genesis.modules.keys() {
+            let file = format!("{module}.json");
+            std::fs::rename(staging.path().join(&file), genesis_dir.join(&file))
+                .with_context(|| format!("Failed to move {file} into {}", genesis_dir.display()))?;
+        }
+
+        std::fs::write(
+            rollup_config_path,
+            toml::to_string_pretty(&self.rollup_config)?,
+        )
+        .with_context(|| {
+            format!(
+                "Failed to write rollup configuration to {}",
+                rollup_config_path.display()
+            )
+        })?;
+
+        if !self.keys.is_empty() {
+            std::fs::create_dir_all(keys_dir)
+                .with_context(|| format!("Failed to create {}", keys_dir.display()))?;
+        }
+        for (name, key) in &self.keys {
+            let path = keys_dir.join(format!("{name}_private_key.json"));
+            std::fs::write(&path, serde_json::to_string_pretty(key)? + "\n")
+                .with_context(|| format!("Failed to write key file {}", path.display()))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn module<'a>(genesis: &'a mut GenesisDocument, name: &str) -> anyhow::Result<&'a mut Value> {
+    genesis
+        .modules
+        .get_mut(name)
+        .with_context(|| format!("The base genesis has no `{name}` module"))
+}
+
+fn read_key<S: Spec>(path: &Path) -> anyhow::Result<PrivateKeyAndAddress<S>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read key file {}", path.display()))?;
+    let key: PrivateKeyAndAddress<S> = serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid key file {}", path.display()))?;
+
+    if !key.is_matching_to_default() {
+        bail!(
+            "The address in {} does not match its private key",
+            path.display()
+        );
+    }
+
+    Ok(key)
+}
+
+fn credential_id<S: Spec>(key: &PrivateKeyAndAddress<S>) -> Value {
+    json!(key.private_key.pub_key().credential_id::<Hasher<S>>())
+}
+
+fn table<'a>(config: &'a mut toml::Table, key: &str) -> Option<&'a mut toml::Table> {
+    config.get_mut(key).and_then(toml::Value::as_table_mut)
+}
@@ -3,3 +3,9 @@ pub mod mock_rollup;
 
 #[cfg(feature = "celestia_da")]
 pub mod celestia_rollup;
+
+pub mod config_overrides;
+pub mod genesis_generator;
+pub mod metrics;
+pub mod prover_mode;
+pub mod soft_confirmations;
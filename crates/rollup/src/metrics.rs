@@ -0,0 +1,82 @@
+//! Prometheus metrics of the node, served on the `--metrics-host`/`--metrics` address.
+//!
+//! The slots are measured by the rollup stats module, see [`rollup_stats::SlotMetrics`]. This
+//! module follows the DA layer and the aggregated proofs, and is started together with the
+//! endpoints of the rollup.
+//!
+//! There is no mempool size metric: the mempool belongs to the sequencer built by
+//! `sov_modules_rollup_blueprint::register_endpoints`, which does not expose it to the node.
+use std::sync::OnceLock;
+
+use prometheus::{register_int_gauge, IntGauge};
+use rollup_stats::SlotMetrics;
+use sov_db::ledger_db::LedgerDb;
+use sov_modules_api::SyncStatus;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::watch;
+
+/// How far the node is from the head of the DA layer.
+pub struct DaMetrics {
+    /// Height of the DA head known to the node.
+    pub head_height: IntGauge,
+    /// Height of the latest DA block processed by the node.
+    pub synced_height: IntGauge,
+}
+
+impl DaMetrics {
+    /// The metrics, registered on first use.
+    pub fn get() -> &'static Self {
+        static METRICS: OnceLock<DaMetrics> = OnceLock::new();
+        METRICS.get_or_init(|| Self {
+            head_height: register_int_gauge!(
+                "rollup_da_head_height",
+                "Height of the DA head known to the node"
+            )
+            .expect("registered once"),
+            synced_height: register_int_gauge!(
+                "rollup_da_synced_height",
+                "Height of the latest DA block processed by the node"
+            )
+            .expect("registered once"),
+        })
+    }
+
+    /// Records the sync status of the node.
+    pub fn record(&self, status: &SyncStatus) {
+        let (synced_height, head_height) = match status {
+            SyncStatus::Syncing {
+                synced_da_height,
+                target_da_height,
+            } => (*synced_da_height, *target_da_height),
+            SyncStatus::Synced { synced_da_height } => (*synced_da_height, *synced_da_height),
+        };
+        self.synced_height
+            .set(i64::try_from(synced_height).unwrap_or(i64::MAX));
+        self.head_height
+            .set(i64::try_from(head_height).unwrap_or(i64::MAX));
+    }
+}
+
+/// Keeps the metrics of the node up to date until it shuts down.
+pub fn spawn_node_metrics(mut sync_status: watch::Receiver<SyncStatus>, ledger_db: &LedgerDb) {
+    tokio::spawn(async move {
+        loop {
+            DaMetrics::get().record(&sync_status.borrow_and_update());
+            if sync_status.changed().await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut proofs = ledger_db.subscribe_proof_saved();
+    tokio::spawn(async move {
+        loop {
+            match proofs.recv().await {
+                Ok(proof) => SlotMetrics::get().record_proof(proof.public_data.final_slot_number),
+                // A skipped proof is covered by the next one.
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
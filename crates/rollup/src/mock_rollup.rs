@@ -115,6 +115,8 @@ impl FullNodeBlueprint<Native> for MockRollup<Native> {
         da_sync_state: Arc<DaSyncState>,
         rollup_config: &RollupConfig<<Self::Spec as Spec>::Address, Self::DaService>,
     ) -> Result<RuntimeEndpoints, Error> {
+        crate::metrics::spawn_node_metrics(sync_status_receiver.clone(), ledger_db);
+
         sov_modules_rollup_blueprint::register_endpoints::<Self, Native>(
             storage.clone(),
             sync_status_receiver,
@@ -0,0 +1,195 @@
+//! Selection of the prover mode when the node starts.
+//!
+//! The mode is taken from, in order of precedence:
+//! 1. the `--prover-mode` flag of the node,
+//! 2. `mode` in the `[prover]` section of `rollup_config.toml`,
+//! 3. the `SOV_PROVER_MODE` environment variable.
+//!
+//! Without any of them, or with the `none` mode, the node runs as a full node without a
+//! prover. The same binary can therefore run as a full node or as a prover.
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_stf_runner::processes::RollupProverConfig;
+
+use crate::config_overrides::{apply_overrides, ConfigOverride};
+
+/// The environment variable read when neither the flag nor the config set the mode.
+pub const PROVER_MODE_ENV_VAR: &str = "SOV_PROVER_MODE";
+
+/// The accepted prover modes.
+pub const PROVER_MODES: [&str; 5] = ["none", "skip", "simulate", "execute", "prove"];
+
+/// The `[prover]` section of `rollup_config.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProverSection {
+    /// One of [`PROVER_MODES`].
+    #[serde(default)]
+    pub mode: Option<String>,
+}
+
+/// Where the prover mode was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProverModeSource {
+    /// The `--prover-mode` flag.
+    Flag,
+    /// The `[prover]` section of the rollup config.
+    Config,
+    /// The [`PROVER_MODE_ENV_VAR`] environment variable.
+    Env,
+    /// Nothing set the mode.
+    Default,
+}
+
+impl fmt::Display for ProverModeSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProverModeSource::Flag => write!(f, "--prover-mode"),
+            ProverModeSource::Config => write!(f, "[prover] mode"),
+            ProverModeSource::Env => write!(f, "{PROVER_MODE_ENV_VAR}"),
+            ProverModeSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// The prover mode of the node.
+#[derive(Debug)]
+pub struct ProverMode {
+    /// The selected mode, one of [`PROVER_MODES`].
+    pub mode: String,
+    /// The prover config, `None` to run without a prover.
+    pub config: Option<RollupProverConfig>,
+    /// Where the mode was taken from.
+    pub source: ProverModeSource,
+}
+
+impl ProverMode {
+    /// Resolves the mode from the flag, the config and the environment, in that order.
+    pub fn resolve(
+        flag: Option<&str>,
+        section: &ProverSection,
+        env: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        let (value, source) = match (flag, section.mode.as_deref(), env) {
+            (Some(value), _, _) => (value, ProverModeSource::Flag),
+            (None, Some(value), _) => (value, ProverModeSource::Config),
+            (None, None, Some(value)) if !value.is_empty() => (value, ProverModeSource::Env),
+            _ => {
+                return Ok(Self {
+                    mode: "none".to_string(),
+                    config: None,
+                    source: ProverModeSource::Default,
+                })
+            }
+        };
+
+        let config =
+            parse_prover_mode(value).with_context(|| format!("Invalid prover mode in {source}"))?;
+
+        Ok(Self {
+            mode: value.to_string(),
+            config,
+            source,
+        })
+    }
+
+    /// Resolves the mode, reading [`PROVER_MODE_ENV_VAR`] from the environment.
+    pub fn from_env(flag: Option<&str>, section: &ProverSection) -> anyhow::Result<Self> {
+        let env = std::env::var(PROVER_MODE_ENV_VAR).ok();
+        Self::resolve(flag, section, env.as_deref())
+    }
+}
+
+/// Parses one of [`PROVER_MODES`], `none` being a node without a prover.
+pub fn parse_prover_mode(value: &str) -> anyhow::Result<Option<RollupProverConfig>> {
+    if value == "none" {
+        return Ok(None);
+    }
+
+    RollupProverConfig::from_str(value)
+        .map(Some)
+        .map_err(|error| {
+            anyhow::anyhow!(
+                "Unknown prover mode `{value}` ({error:?}), expected one of: {}",
+                PROVER_MODES.join(", ")
+            )
+        })
+}
+
+/// Reads `rollup_config.toml` with `overrides` applied, see [`crate::config_overrides`], and
+/// splits off the `[prover]` section from the rest of the config.
+pub fn read_rollup_config<C: DeserializeOwned + Serialize>(
+    path: &Path,
+    overrides: &[ConfigOverride],
+) -> anyhow::Result<(C, ProverSection)> {
+    let contents = std::fs::read_to_string(path).with_context(|| {
+        format!(
+            "Failed to read rollup configuration from {}",
+            path.display()
+        )
+    })?;
+    let mut config: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("Invalid rollup configuration {}", path.display()))?;
+    let known = known_fields::<C>(&config);
+    apply_overrides(&mut config, &known, overrides)?;
+
+    let prover = match config.remove("prover") {
+        Some(section) => section
+            .try_into()
+            .with_context(|| format!("Invalid [prover] section in {}", path.display()))?,
+        None => ProverSection::default(),
+    };
+    let config = toml::Value::Table(config)
+        .try_into()
+        .with_context(|| format!("Invalid rollup configuration {}", path.display()))?;
+
+    Ok((config, prover))
+}
+
+/// The fields of `config` that may be overridden, each with a value of its type: the fields of
+/// the file, plus those filled in by a default when deserializing it as `C`.
+fn known_fields<C: DeserializeOwned + Serialize>(config: &toml::Table) -> toml::Table {
+    let mut known = config.clone();
+
+    let mut rollup_config = config.clone();
+    rollup_config.remove("prover");
+    // Without a complete file, only its own fields are known.
+    let defaults = toml::Value::Table(rollup_config)
+        .try_into::<C>()
+        .ok()
+        .and_then(|typed| toml::Table::try_from(&typed).ok());
+    if let Some(defaults) = defaults {
+        merge_missing(&mut known, defaults);
+    }
+
+    let prover = ProverSection {
+        mode: Some(String::new()),
+    };
+    let prover = toml::Table::try_from(&prover).expect("The prover section is a table");
+    merge_missing(
+        &mut known,
+        [("prover".to_string(), prover.into())]
+            .into_iter()
+            .collect(),
+    );
+
+    known
+}
+
+/// Adds the fields of `from` missing in `into`, recursively.
+fn merge_missing(into: &mut toml::Table, from: toml::Table) {
+    for (key, value) in from {
+        match (into.get_mut(&key), value) {
+            (Some(toml::Value::Table(into)), toml::Value::Table(from)) => merge_missing(into, from),
+            (Some(_), _) => {}
+            (None, value) => {
+                into.insert(key, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Node settings required by the soft-confirmations kernel.
+//!
+//! The kernel is selected when building the runtime, with the `soft-confirmations` feature of
+//! this crate. Batches from the preferred sequencer (`is_preferred_sequencer` in
+//! `sequencer_registry.json`) are executed immediately; batches from any other sender are
+//! "forced" and executed at the latest `DEFERRED_SLOTS_COUNT` slots after they land on the DA
+//! layer.
+use sov_modules_api::macros::config_value;
+use stf_starter::SOFT_CONFIRMATIONS;
+
+/// The maximum number of slots a forced batch can be deferred by, from `constants.toml`.
+pub fn deferred_slots_count() -> u64 {
+    config_value!("DEFERRED_SLOTS_COUNT")
+}
+
+/// Fails if the sequencer settings do not work with the kernel the runtime was built with.
+///
+/// With soft confirmations, the preferred sequencer must not fall further behind the DA layer
+/// than the deferral window: forced batches would otherwise be executed before the batches it
+/// already confirmed, invalidating its soft confirmations.
+pub fn check_sequencer_settings(max_allowed_blocks_behind: u64) -> anyhow::Result<()> {
+    let deferred_slots_count = deferred_slots_count();
+
+    if SOFT_CONFIRMATIONS && max_allowed_blocks_behind > deferred_slots_count {
+        anyhow::bail!(
+            "`sequencer.max_allowed_blocks_behind` ({max_allowed_blocks_behind}) must not exceed \
+             `DEFERRED_SLOTS_COUNT` ({deferred_slots_count}) when running with soft confirmations"
+        );
+    }
+
+    Ok(())
+}
@@ -1,3 +1,10 @@
 mod bank;
+mod config_overrides;
+mod genesis_generator;
+mod metrics;
+mod node_cli;
+mod prover_mode;
+#[cfg(feature = "soft-confirmations")]
+mod soft_confirmations;
 // Add additional tests here
 mod test_helpers;
@@ -0,0 +1,169 @@
+use sha2::Sha256;
+use sov_mock_da::storable::service::StorableMockDaService;
+use sov_mock_da::MockAddress;
+use sov_modules_api::Address;
+use sov_rollup_interface::node::da::DaServiceWithRetries;
+use sov_rollup_starter::config_overrides::{
+    apply_overrides, collect_overrides, redact_secrets, ConfigOverride, OverrideSource, REDACTED,
+};
+use sov_rollup_starter::prover_mode::read_rollup_config;
+use sov_stf_runner::RollupConfig;
+
+type MockRollupConfig = RollupConfig<Address<Sha256>, DaServiceWithRetries<StorableMockDaService>>;
+
+fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+    vars.iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_env_var_names_map_to_paths() {
+    let overrides = collect_overrides(
+        env(&[
+            ("TMC_RUNNER__RPC_CONFIG__BIND_PORT", "1"),
+            ("TMC_DA__CONNECTION_STRING", "sqlite::memory:"),
+            ("PATH", "/usr/bin"),
+        ]),
+        &[],
+    );
+
+    let keys: Vec<_> = overrides.iter().map(|o| o.key.as_str()).collect();
+    assert_eq!(
+        keys,
+        ["da.connection_string", "runner.rpc_config.bind_port"]
+    );
+    assert_eq!(
+        overrides[0].source,
+        OverrideSource::Env("TMC_DA__CONNECTION_STRING".to_string())
+    );
+}
+
+#[test]
+fn test_flags_take_precedence() -> anyhow::Result<()> {
+    let flag: ConfigOverride = "runner.rpc_config.bind_port=3".parse()?;
+    assert_eq!(flag.source, OverrideSource::Flag);
+    let overrides = collect_overrides(env(&[("TMC_RUNNER__RPC_CONFIG__BIND_PORT", "2")]), &[flag]);
+
+    let mut config: toml::Table = toml::from_str("[runner.rpc_config]\nbind_port = 1")?;
+    let known = config.clone();
+    apply_overrides(&mut config, &known, &overrides)?;
+    assert_eq!(
+        config["runner"]["rpc_config"]["bind_port"].as_integer(),
+        Some(3)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_values_keep_the_type_of_the_field() -> anyhow::Result<()> {
+    let known: toml::Table = toml::from_str(
+        "[da]\nsender_address = \"00\"\nblock_time_ms = 1\n[storage]\npath = \"data\"",
+    )?;
+    // `block_time_ms` and `storage` are only known from the defaults.
+    let mut config: toml::Table = toml::from_str("[da]\nsender_address = \"00\"")?;
+    let overrides = [
+        "da.sender_address=0101".parse()?,
+        "da.block_time_ms=500".parse()?,
+        "storage.path=/data/rollup".parse()?,
+    ];
+    apply_overrides(&mut config, &known, &overrides)?;
+
+    assert_eq!(config["da"]["sender_address"].as_str(), Some("0101"));
+    assert_eq!(config["da"]["block_time_ms"].as_integer(), Some(500));
+    assert_eq!(config["storage"]["path"].as_str(), Some("/data/rollup"));
+
+    let overrides = ["da.block_time_ms=fast".parse()?];
+    let error = apply_overrides(&mut config, &known, &overrides).unwrap_err();
+    let message = format!("{error:#}");
+    assert!(message.contains("Expected integer"), "{message}");
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_overrides_are_rejected() -> anyhow::Result<()> {
+    assert!("runner.genesis_height".parse::<ConfigOverride>().is_err());
+
+    let mut config: toml::Table = toml::from_str("[runner]\ngenesis_height = 0")?;
+    let known = config.clone();
+    let overrides = [
+        "runner.genesis_height.value=1".parse()?,
+        "runner.unknown=1".parse()?,
+    ];
+    let error = apply_overrides(&mut config, &known, &overrides).unwrap_err();
+    let message = format!("{error:#}");
+    assert!(
+        message.contains("`runner.genesis_height.value` from --set"),
+        "{message}"
+    );
+    assert!(message.contains("`runner.unknown` from --set"), "{message}");
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_env_vars_are_ignored() -> anyhow::Result<()> {
+    let mut config: toml::Table = toml::from_str("[runner]\ngenesis_height = 0")?;
+    let known = config.clone();
+    let overrides = collect_overrides(
+        env(&[
+            ("TMC_UNRELATED__SETTING", "1"),
+            ("TMC_RUNNER__GENESIS_HEIGHT", "5"),
+        ]),
+        &[],
+    );
+    apply_overrides(&mut config, &known, &overrides)?;
+
+    assert_eq!(config, toml::from_str("[runner]\ngenesis_height = 5")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_rollup_config_is_overridden() -> anyhow::Result<()> {
+    let overrides = [
+        "runner.rpc_config.bind_port=23456".parse()?,
+        "storage.path=/data/rollup".parse()?,
+        "prover.mode=skip".parse()?,
+        // Hex digits only, but the address is a string.
+        format!("da.sender_address={}", "01".repeat(32)).parse()?,
+    ];
+    let (rollup_config, prover): (MockRollupConfig, _) =
+        read_rollup_config("../../rollup_config.toml".as_ref(), &overrides)?;
+
+    assert_eq!(rollup_config.runner.rpc_config.bind_port, 23456);
+    assert_eq!(
+        rollup_config.storage.path,
+        std::path::Path::new("/data/rollup")
+    );
+    assert_eq!(prover.mode.as_deref(), Some("skip"));
+    assert_eq!(rollup_config.da.sender_address, MockAddress::new([1; 32]));
+
+    Ok(())
+}
+
+#[test]
+fn test_secrets_are_redacted() -> anyhow::Result<()> {
+    let mut config: toml::Table = toml::from_str(&std::fs::read_to_string(
+        "../../celestia_rollup_config.toml",
+    )?)?;
+    redact_secrets(&mut config);
+    assert_eq!(
+        config["da"]["celestia_rpc_auth_token"].as_str(),
+        Some(REDACTED)
+    );
+    assert_eq!(
+        config["da"]["celestia_rpc_address"].as_str(),
+        Some("http://127.0.0.1:26658")
+    );
+
+    // Configs without the secret fields are left as they are.
+    let mut config: toml::Table = toml::from_str("[runner]\ngenesis_height = 0")?;
+    let expected = config.clone();
+    redact_secrets(&mut config);
+    assert_eq!(config, expected);
+
+    Ok(())
+}
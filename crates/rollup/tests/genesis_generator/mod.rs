@@ -0,0 +1,191 @@
+use super::test_helpers::read_private_keys;
+use sov_mock_da::MockDaSpec;
+use sov_modules_api::execution_mode::Native;
+use sov_rollup_starter::genesis_generator::NetworkSpec;
+use sov_test_utils::MockZkVerifier;
+use stf_starter::genesis_config::{GenesisDocument, GenesisSource};
+
+type TestSpec =
+    sov_modules_api::default_spec::DefaultSpec<MockDaSpec, MockZkVerifier, MockZkVerifier, Native>;
+
+const SEQUENCER_DA_ADDRESS: &str =
+    "0101010101010101010101010101010101010101010101010101010101010101";
+
+fn spec() -> NetworkSpec {
+    toml::from_str(&format!(
+        r#"
+        token_name = "net-token"
+        governor = "signer"
+
+        [[accounts]]
+        name = "operator"
+        balance = 1_000_000_000
+
+        [[accounts]]
+        name = "signer"
+        balance = 500
+        key_file = "../../test-data/keys/tx_signer_private_key.json"
+
+        [sequencer]
+        account = "operator"
+        da_address = "{SEQUENCER_DA_ADDRESS}"
+        bond = 10_000_000
+
+        [[provers]]
+        account = "operator"
+        bond = 200_000
+
+        [chain]
+        evm_chain_id = 77
+        genesis_da_height = 12
+        "#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_generated_network_is_consistent() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let genesis_dir = dir.path().join("genesis");
+    let rollup_config_path = dir.path().join("rollup_config.toml");
+    let keys_dir = dir.path().join("keys");
+
+    let network = spec().generate::<TestSpec>(
+        &GenesisSource::from_path("../../test-data/genesis/mock/"),
+        "../../rollup_config.toml".as_ref(),
+    )?;
+    // Validates the genesis before writing it.
+    network.write(&genesis_dir, &rollup_config_path, &keys_dir, false)?;
+
+    // Only the account without a key file or address gets a new key.
+    let operator = read_private_keys_in::<TestSpec>(&keys_dir.join("operator_private_key.json"));
+    assert_eq!(network.keys.len(), 1);
+    let operator = serde_json::to_value(operator.address)?;
+    let signer =
+        serde_json::to_value(read_private_keys::<TestSpec>("tx_signer_private_key.json").address)?;
+
+    let genesis = GenesisDocument::read(&GenesisSource::from_path(&genesis_dir))?;
+    let modules = &genesis.modules;
+    assert_eq!(
+        modules["bank"]["gas_token_config"]["address_and_balances"],
+        serde_json::json!([[operator, 1_000_000_000], [signer, 500]])
+    );
+    assert_eq!(
+        modules["bank"]["gas_token_config"]["token_name"],
+        "net-token"
+    );
+    assert_eq!(modules["accounts"]["accounts"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        modules["sequencer_registry"]["seq_rollup_address"],
+        operator
+    );
+    assert_eq!(
+        modules["sequencer_registry"]["seq_da_address"],
+        SEQUENCER_DA_ADDRESS
+    );
+    assert_eq!(modules["governance"]["governor"], signer);
+    assert_eq!(modules["evm"]["chain_id"], 77);
+    assert_eq!(modules["chain_state"]["genesis_da_height"], 12);
+
+    let rollup_config: toml::Table =
+        toml::from_str(&std::fs::read_to_string(&rollup_config_path)?)?;
+    assert_eq!(
+        rollup_config["sequencer"]["da_address"].as_str(),
+        Some(SEQUENCER_DA_ADDRESS)
+    );
+    assert_eq!(
+        rollup_config["da"]["sender_address"].as_str(),
+        Some(SEQUENCER_DA_ADDRESS)
+    );
+    assert_eq!(
+        rollup_config["runner"]["genesis_height"].as_integer(),
+        Some(12)
+    );
+    assert_eq!(
+        rollup_config["proof_manager"]["prover_address"].as_str(),
+        operator.as_str()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_network_is_not_written() -> anyhow::Result<()> {
+    let mut spec = spec();
+    // More than the operator holds.
+    spec.sequencer.bond = 2_000_000_000;
+
+    let dir = tempfile::tempdir()?;
+    let network = spec.generate::<TestSpec>(
+        &GenesisSource::from_path("../../test-data/genesis/mock/"),
+        "../../rollup_config.toml".as_ref(),
+    )?;
+    let result = network.write(
+        &dir.path().join("genesis"),
+        &dir.path().join("rollup_config.toml"),
+        &dir.path().join("keys"),
+        false,
+    );
+
+    assert!(result.is_err());
+    assert_eq!(std::fs::read_dir(dir.path())?.count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_existing_files_are_only_overwritten_with_force() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let genesis_dir = dir.path().join("genesis");
+    let rollup_config_path = dir.path().join("rollup_config.toml");
+    let keys_dir = dir.path().join("keys");
+
+    let network = spec().generate::<TestSpec>(
+        &GenesisSource::from_path("../../test-data/genesis/mock/"),
+        "../../rollup_config.toml".as_ref(),
+    )?;
+    network.write(&genesis_dir, &rollup_config_path, &keys_dir, false)?;
+    let key_path = keys_dir.join("operator_private_key.json");
+    let key = std::fs::read_to_string(&key_path)?;
+
+    // A second network would replace the operator key, and lose the funds of the first one.
+    let other = spec().generate::<TestSpec>(
+        &GenesisSource::from_path("../../test-data/genesis/mock/"),
+        "../../rollup_config.toml".as_ref(),
+    )?;
+    let error = other
+        .write(&genesis_dir, &rollup_config_path, &keys_dir, false)
+        .err()
+        .expect("The files already exist");
+    assert!(error.to_string().contains("--force"), "{error}");
+    assert_eq!(std::fs::read_to_string(&key_path)?, key);
+
+    other.write(&genesis_dir, &rollup_config_path, &keys_dir, true)?;
+    assert_ne!(std::fs::read_to_string(&key_path)?, key);
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_account_is_rejected() {
+    let mut spec = spec();
+    spec.sequencer.account = "nobody".to_string();
+
+    let error = spec
+        .generate::<TestSpec>(
+            &GenesisSource::from_path("../../test-data/genesis/mock/"),
+            "../../rollup_config.toml".as_ref(),
+        )
+        .err()
+        .expect("The sequencer account does not exist");
+    assert!(error.to_string().contains("nobody"), "{error}");
+}
+
+fn read_private_keys_in<S: sov_modules_api::Spec>(
+    path: &std::path::Path,
+) -> sov_cli::wallet_state::PrivateKeyAndAddress<S> {
+    let key: sov_cli::wallet_state::PrivateKeyAndAddress<S> =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    assert!(key.is_matching_to_default(), "Inconsistent key data");
+    key
+}
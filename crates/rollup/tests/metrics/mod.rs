@@ -0,0 +1,20 @@
+use sov_modules_api::SyncStatus;
+use sov_rollup_starter::metrics::DaMetrics;
+
+#[test]
+fn test_da_heights() {
+    let metrics = DaMetrics::get();
+
+    metrics.record(&SyncStatus::Syncing {
+        synced_da_height: 3,
+        target_da_height: 10,
+    });
+    assert_eq!(metrics.synced_height.get(), 3);
+    assert_eq!(metrics.head_height.get(), 10);
+
+    metrics.record(&SyncStatus::Synced {
+        synced_da_height: 10,
+    });
+    assert_eq!(metrics.synced_height.get(), 10);
+    assert_eq!(metrics.head_height.get(), 10);
+}
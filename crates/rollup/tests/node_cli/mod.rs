@@ -0,0 +1,55 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Runs the `node` binary with the config and genesis of `dir`.
+fn node(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_node"))
+        .arg("--rollup-config-path")
+        .arg(dir.join("rollup_config.toml"))
+        .arg("--genesis-paths")
+        .arg(dir.join("genesis"))
+        .args(args)
+        .output()
+        .expect("The node binary runs")
+}
+
+fn init(dir: &Path, extra_args: &[&str]) -> Output {
+    let data_dir = dir.join("data");
+    let mut args = vec!["init", "--data-dir", data_dir.to_str().unwrap()];
+    args.extend(extra_args);
+    node(dir, &args)
+}
+
+#[test]
+fn test_initialized_node_is_valid() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let output = init(dir.path(), &[]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(dir.path().join("data").is_dir());
+    assert!(dir.path().join("genesis/bank.json").is_file());
+
+    let output = node(dir.path(), &["validate-config"]);
+    assert!(output.status.success(), "{output:?}");
+
+    Ok(())
+}
+
+#[test]
+fn test_init_does_not_overwrite_without_force() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    assert!(init(dir.path(), &[]).status.success());
+
+    let config_path = dir.path().join("rollup_config.toml");
+    std::fs::write(&config_path, "# Edited by hand\n")?;
+
+    let output = init(dir.path(), &[]);
+    assert!(!output.status.success(), "{output:?}");
+    assert_eq!(std::fs::read_to_string(&config_path)?, "# Edited by hand\n");
+
+    let output = init(dir.path(), &["--force"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_ne!(std::fs::read_to_string(&config_path)?, "# Edited by hand\n");
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use sha2::Sha256;
+use sov_mock_da::storable::service::StorableMockDaService;
+use sov_modules_api::Address;
+use sov_rollup_interface::node::da::DaServiceWithRetries;
+use sov_rollup_starter::prover_mode::{
+    read_rollup_config, ProverMode, ProverModeSource, ProverSection,
+};
+use sov_stf_runner::processes::RollupProverConfig;
+use sov_stf_runner::RollupConfig;
+
+fn section(mode: Option<&str>) -> ProverSection {
+    ProverSection {
+        mode: mode.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_flag_takes_precedence() {
+    let mode = ProverMode::resolve(Some("skip"), &section(Some("prove")), Some("execute")).unwrap();
+    assert_eq!(mode.source, ProverModeSource::Flag);
+    assert!(matches!(mode.config, Some(RollupProverConfig::Skip)));
+
+    let mode = ProverMode::resolve(None, &section(Some("skip")), Some("execute")).unwrap();
+    assert_eq!(mode.source, ProverModeSource::Config);
+    assert!(matches!(mode.config, Some(RollupProverConfig::Skip)));
+
+    let mode = ProverMode::resolve(None, &section(None), Some("skip")).unwrap();
+    assert_eq!(mode.source, ProverModeSource::Env);
+    assert!(matches!(mode.config, Some(RollupProverConfig::Skip)));
+}
+
+#[test]
+fn test_full_node_without_prover() {
+    let mode = ProverMode::resolve(None, &section(None), None).unwrap();
+    assert_eq!(mode.source, ProverModeSource::Default);
+    assert!(mode.config.is_none());
+
+    // An empty variable is the same as an unset one.
+    let mode = ProverMode::resolve(None, &section(None), Some("")).unwrap();
+    assert_eq!(mode.source, ProverModeSource::Default);
+
+    // `none` turns off a prover enabled by a lower precedence source.
+    let mode = ProverMode::resolve(Some("none"), &section(None), Some("prove")).unwrap();
+    assert_eq!(mode.source, ProverModeSource::Flag);
+    assert!(mode.config.is_none());
+}
+
+#[test]
+fn test_unknown_mode_is_rejected() {
+    let error = ProverMode::resolve(None, &section(Some("fast")), None).unwrap_err();
+    let message = format!("{error:#}");
+    assert!(message.contains("[prover] mode"), "{message}");
+    assert!(message.contains("fast"), "{message}");
+}
+
+#[test]
+fn test_prover_section_is_split_off() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("rollup_config.toml");
+    let mut config: toml::Table =
+        toml::from_str(&std::fs::read_to_string("../../rollup_config.toml")?)?;
+    config
+        .entry("prover")
+        .or_insert_with(|| toml::Table::new().into())
+        .as_table_mut()
+        .expect("The prover section is a table")
+        .insert("mode".to_string(), "skip".into());
+    std::fs::write(&path, toml::to_string(&config)?)?;
+
+    let (rollup_config, prover): (
+        RollupConfig<Address<Sha256>, DaServiceWithRetries<StorableMockDaService>>,
+        _,
+    ) = read_rollup_config(&path, &[])?;
+    assert_eq!(prover.mode.as_deref(), Some("skip"));
+    assert_eq!(rollup_config.runner.genesis_height, 0);
+
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use super::test_helpers::{read_private_keys, start_rollup};
+use anyhow::Context;
+use futures::StreamExt;
+use sov_cli::NodeClient;
+use sov_mock_da::storable::service::StorableMockDaService;
+use sov_mock_da::{BlockProducingConfig, MockAddress, MockDaConfig, MockDaSpec};
+use sov_modules_api::execution_mode::Native;
+use sov_modules_api::macros::config_value;
+use sov_modules_api::transaction::{PriorityFeeBips, Transaction, UnsignedTransaction};
+use sov_modules_api::{Batch, BlobData, RawTx, Spec};
+use sov_rollup_interface::da::BlobReaderTrait;
+use sov_rollup_interface::node::da::DaService;
+use sov_rollup_starter::soft_confirmations::deferred_slots_count;
+use sov_stf_runner::processes::RollupProverConfig;
+use sov_test_utils::MockZkVerifier;
+use std::path::Path;
+use stf_starter::authentication::Auth;
+use stf_starter::genesis_config::GenesisPaths;
+use stf_starter::RuntimeCall;
+
+const TOKEN_NAME: &str = "forced-token";
+const MAX_TX_FEE: u64 = 100_000_000;
+
+/// The DA address of the forced batch's sender, which is not a registered sequencer.
+const FORCED_SENDER: MockAddress = MockAddress::new([7; 32]);
+
+type TestSpec =
+    sov_modules_api::default_spec::DefaultSpec<MockDaSpec, MockZkVerifier, MockZkVerifier, Native>;
+
+/// Copies the mock genesis into `dir`, letting unregistered senders create tokens so that
+/// the forced transaction is accepted.
+fn write_genesis(dir: &Path) -> anyhow::Result<GenesisPaths> {
+    for entry in std::fs::read_dir("../../test-data/genesis/mock/")? {
+        let entry = entry?;
+        std::fs::copy(entry.path(), dir.join(entry.file_name()))?;
+    }
+
+    let governance_path = dir.join("governance.json");
+    let mut governance: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&governance_path)?)?;
+    governance["unregistered_calls"] = serde_json::json!([
+        { "module": "bank", "call": "create_token" }
+    ]);
+    std::fs::write(&governance_path, serde_json::to_string_pretty(&governance)?)?;
+
+    Ok(GenesisPaths::from_dir(dir))
+}
+
+fn da_config(connection_string: &str, sender_address: MockAddress) -> MockDaConfig {
+    MockDaConfig {
+        connection_string: connection_string.to_string(),
+        sender_address,
+        finalization_blocks: 0,
+        block_producing: BlockProducingConfig::Periodic,
+        block_time_ms: 500,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn forced_batches_are_executed_after_the_deferral_window() -> Result<(), anyhow::Error> {
+    let dir = tempfile::tempdir()?;
+    let genesis_paths = write_genesis(dir.path())?;
+    // The node and the forced sender share the same DA layer.
+    let connection_string = format!(
+        "sqlite://{}?mode=rwc",
+        dir.path().join("mock_da.sqlite").display()
+    );
+
+    let (rpc_port_tx, rpc_port_rx) = tokio::sync::oneshot::channel();
+    let (rest_port_tx, rest_port_rx) = tokio::sync::oneshot::channel();
+
+    let node_da_config = da_config(&connection_string, MockAddress::new([0; 32]));
+    let rollup_task = tokio::spawn(async move {
+        start_rollup(
+            rpc_port_tx,
+            rest_port_tx,
+            genesis_paths,
+            RollupProverConfig::Skip,
+            node_da_config,
+        )
+        .await;
+    });
+    let _ = rpc_port_rx.await;
+    let rest_port = rest_port_rx.await?.port();
+    let client = NodeClient::new_at_localhost(rest_port).await?;
+
+    let forced_da_service =
+        StorableMockDaService::from_config(da_config(&connection_string, FORCED_SENDER)).await;
+
+    // If the rollup throws an error, return it and stop waiting for the forced batch
+    tokio::select! {
+        err = rollup_task => err?,
+        res = send_forced_batch(&client, &forced_da_service) => res?,
+    }
+    Ok(())
+}
+
+async fn send_forced_batch(
+    client: &NodeClient,
+    da_service: &StorableMockDaService,
+) -> Result<(), anyhow::Error> {
+    let key_and_address = read_private_keys::<TestSpec>("tx_signer_private_key.json");
+    let user_address: <TestSpec as Spec>::Address = key_and_address.address;
+    let token_id = sov_bank::get_token_id::<TestSpec>(TOKEN_NAME, &user_address);
+    let initial_balance = 1000;
+
+    let msg = RuntimeCall::<TestSpec>::Bank(sov_bank::CallMessage::<TestSpec>::CreateToken {
+        token_name: TOKEN_NAME.to_string(),
+        initial_balance,
+        mint_to_address: user_address,
+        authorized_minters: vec![],
+    });
+    let tx = Transaction::<TestSpec>::new_signed_tx(
+        &key_and_address.private_key,
+        UnsignedTransaction::new(
+            borsh::to_vec(&msg)?,
+            config_value!("CHAIN_ID"),
+            PriorityFeeBips::ZERO,
+            MAX_TX_FEE,
+            0,
+            None,
+        ),
+    );
+    let raw_tx = RawTx {
+        data: borsh::to_vec(&Auth::Mod(borsh::to_vec(&tx)?))?,
+    };
+    let blob = borsh::to_vec(&BlobData::Batch(Batch { txs: vec![raw_tx] }))?;
+
+    let mut slot_subscription = client
+        .ledger
+        .subscribe_slots()
+        .await
+        .context("Failed to subscribe to slots!")?;
+
+    // Post the batch directly to the DA layer, the preferred sequencer never sees it.
+    da_service
+        .send_transaction(&blob)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to submit the forced batch: {e:?}"))?;
+
+    let submitted_at = slot_subscription
+        .next()
+        .await
+        .transpose()?
+        .map(|slot| slot.number)
+        .context("The slot subscription ended")?;
+    let deferred_slots_count = deferred_slots_count();
+
+    loop {
+        let slot = slot_subscription
+            .next()
+            .await
+            .transpose()?
+            .map(|slot| slot.number)
+            .context("The slot subscription ended")?;
+        let balance = client
+            .get_balance::<TestSpec>(&user_address, &token_id, None)
+            .await
+            .unwrap_or_default();
+
+        if balance == initial_balance {
+            // The first slot seen after submitting may have been produced just before the
+            // batch was posted, so look up the block that actually holds it. Nobody included
+            // the batch earlier, so it must have waited for the whole window.
+            let posted_at = forced_batch_height(da_service, submitted_at, slot).await?;
+            assert_eq!(
+                slot,
+                posted_at + deferred_slots_count,
+                "Forced batch posted at slot {posted_at} was executed at slot {slot}"
+            );
+            return Ok(());
+        }
+
+        assert!(
+            slot <= submitted_at + deferred_slots_count + 1,
+            "Forced batch submitted at slot {submitted_at} is still not executed at slot {slot}"
+        );
+    }
+}
+
+/// The height of the first DA block between `from` and `to` holding a batch of the forced
+/// sender. Slots are numbered after the heights of the mock DA blocks.
+async fn forced_batch_height(
+    da_service: &StorableMockDaService,
+    from: u64,
+    to: u64,
+) -> Result<u64, anyhow::Error> {
+    for height in from..=to {
+        let block = da_service
+            .get_block_at(height)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read DA block {height}: {e:?}"))?;
+        let blobs = da_service.extract_relevant_blobs(&block);
+        if blobs
+            .batch_blobs
+            .iter()
+            .any(|blob| blob.sender() == FORCED_SENDER)
+        {
+            return Ok(height);
+        }
+    }
+
+    anyhow::bail!("The forced batch is not in the DA blocks {from} to {to}")
+}
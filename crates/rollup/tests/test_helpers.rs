@@ -14,7 +14,7 @@ use sov_stf_runner::processes::RollupProverConfig;
 use sov_stf_runner::{HttpServerConfig, ProofManagerConfig};
 use sov_stf_runner::{RollupConfig, RunnerConfig, StorageConfig};
 use std::str::FromStr;
-use stf_starter::genesis_config::GenesisPaths;
+use stf_starter::genesis_config::GenesisSource;
 use tokio::sync::oneshot;
 
 const PROVER_ADDRESS: &str = "sov1pv9skzctpv9skzctpv9skzctpv9skzctpv9skzctpv9skzctpv9stup8tx";
@@ -22,7 +22,7 @@ const PROVER_ADDRESS: &str = "sov1pv9skzctpv9skzctpv9skzctpv9skzctpv9skzctpv9skz
 pub async fn start_rollup(
     rpc_reporting_channel: oneshot::Sender<SocketAddr>,
     rest_reporting_channel: oneshot::Sender<SocketAddr>,
-    rt_genesis_paths: GenesisPaths,
+    rt_genesis_paths: impl Into<GenesisSource>,
     rollup_prover_config: RollupProverConfig,
     da_config: MockDaConfig,
 ) {
@@ -62,7 +62,11 @@ pub async fn start_rollup(
     let mock_demo_rollup = MockRollup::default();
 
     let rollup = mock_demo_rollup
-        .create_new_rollup(&rt_genesis_paths, rollup_config, Some(rollup_prover_config))
+        .create_new_rollup(
+            &rt_genesis_paths.into(),
+            rollup_config,
+            Some(rollup_prover_config),
+        )
         .await
         .unwrap();
 
@@ -0,0 +1,160 @@
+use std::fmt::Debug;
+
+use anyhow::Result;
+use sov_modules_api::macros::UniversalWallet;
+use sov_modules_api::prelude::*;
+#[cfg(feature = "native")]
+use sov_modules_api::schemars;
+use sov_modules_api::{CallResponse, Context, CredentialId, EventEmitter, Spec, TxState};
+
+use crate::error::SessionError;
+use crate::event::Event;
+use crate::{SessionGrant, SessionKeys};
+
+/// The runtime modules session keys can never be allowed to call: this module, otherwise
+/// they could grant themselves broader permissions, `accounts`, otherwise they could register
+/// their credential for the granter's account, `governance`, whose powers must stay with the
+/// governor's own key, and the modules moving the granter's funds. The spend limit of a grant
+/// only bounds the gas the session key pays and the fees it pays to `example_module`.
+pub const FORBIDDEN_MODULES: [&str; 9] = [
+    "session_keys",
+    "accounts",
+    "governance",
+    "bank",
+    "paymaster",
+    "evm",
+    "sequencer_registry",
+    "attester_incentives",
+    "prover_incentives",
+];
+
+/// This enumeration represents the available call messages for interacting with
+/// the `SessionKeys` module.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    schemars(rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "arbitrary",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Debug,
+    PartialEq,
+    UniversalWallet,
+    Clone,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CallMessage {
+    /// Lets the key with credential `session` act for the sender.
+    Grant {
+        /// The credential of the session key.
+        session: CredentialId,
+        /// Names of the runtime modules the session key can call.
+        modules: Vec<String>,
+        /// Maximum sum of the gas fees and module fees the session key can spend.
+        spend_limit: u64,
+        /// The session key is rejected from this rollup height on.
+        expires_at: u64,
+    },
+    /// Revokes a session key granted by the sender.
+    Revoke {
+        /// The credential of the session key.
+        session: CredentialId,
+    },
+}
+
+impl<S: Spec> SessionKeys<S> {
+    /// Records a new grant from the sender.
+    pub(crate) fn grant(
+        &self,
+        session: CredentialId,
+        modules: Vec<String>,
+        spend_limit: u64,
+        expires_at: u64,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        let granter = context.sender().clone();
+        let key = (granter.clone(), session);
+
+        if self.grants.get(&key, state)?.is_some() {
+            return Err(SessionError::<S::Address>::AlreadyGranted { session }.into());
+        }
+        if modules.is_empty() {
+            return Err(SessionError::<S::Address>::NoModules.into());
+        }
+        if let Some(module) = modules
+            .iter()
+            .find(|module| FORBIDDEN_MODULES.contains(&module.as_str()))
+        {
+            return Err(SessionError::<S::Address>::ForbiddenModule {
+                module: module.clone(),
+            }
+            .into());
+        }
+        // Expiry is checked against the true slot number of `chain_state` when the session
+        // key is used. The visible slot number never runs ahead of it, so a grant rejected
+        // here would be expired anyway.
+        if expires_at <= context.visible_slot_number().get() {
+            return Err(SessionError::<S::Address>::AlreadyExpired { expires_at }.into());
+        }
+
+        self.grants.set(
+            &key,
+            &SessionGrant {
+                modules: modules.clone(),
+                spend_limit,
+                spent: 0,
+                expires_at,
+            },
+            state,
+        )?;
+        self.emit_event(
+            state,
+            Event::SessionGranted {
+                granter,
+                session,
+                modules,
+                spend_limit,
+                expires_at,
+            },
+        );
+
+        Ok(CallResponse::default())
+    }
+
+    /// Revokes a grant of the sender.
+    pub(crate) fn revoke(
+        &self,
+        session: CredentialId,
+        context: &Context<S>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse> {
+        let granter = context.sender();
+        let key = (granter.clone(), session);
+
+        if self.grants.remove(&key, state)?.is_none() {
+            return Err(SessionError::UnknownSession {
+                granter: granter.clone(),
+                session,
+            }
+            .into());
+        }
+
+        self.emit_event(
+            state,
+            Event::SessionRevoked {
+                granter: granter.clone(),
+                session,
+            },
+        );
+
+        Ok(CallResponse::default())
+    }
+}
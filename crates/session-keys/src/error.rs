@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+use sov_modules_api::CredentialId;
+
+/// Errors returned by the [`crate::SessionKeys`] module.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SessionError<Address: Display> {
+    /// The sender already granted this session key.
+    #[error("Session key {session:?} was already granted")]
+    AlreadyGranted {
+        /// The credential of the session key.
+        session: CredentialId,
+    },
+    /// A grant must allow at least one module.
+    #[error("A session key must be allowed to call at least one module")]
+    NoModules,
+    /// Session keys cannot be allowed to call this module.
+    #[error("Session keys cannot call module `{module}`")]
+    ForbiddenModule {
+        /// The name of the module.
+        module: String,
+    },
+    /// The expiry height is in the past.
+    #[error("Expiry height {expires_at} has already passed")]
+    AlreadyExpired {
+        /// The requested expiry height.
+        expires_at: u64,
+    },
+    /// The granter has no active grant for this session key.
+    #[error("{granter} has no active session key {session:?}")]
+    UnknownSession {
+        /// The account the session key claims to act for.
+        granter: Address,
+        /// The credential of the session key.
+        session: CredentialId,
+    },
+    /// The session key expired.
+    #[error("Session key {session:?} expired at height {expires_at}")]
+    Expired {
+        /// The credential of the session key.
+        session: CredentialId,
+        /// The expiry height of the grant.
+        expires_at: u64,
+    },
+    /// The session key is not allowed to call the module.
+    #[error("Session key {session:?} cannot call module `{module}`")]
+    OutOfScope {
+        /// The credential of the session key.
+        session: CredentialId,
+        /// The module called by the transaction.
+        module: String,
+    },
+    /// The maximum fee of the transaction, with the module fees it pays, could exceed the
+    /// spend limit of the session key.
+    #[error("Session key {session:?} could spend {requested}, above its limit of {spend_limit}")]
+    SpendLimitExceeded {
+        /// The credential of the session key.
+        session: CredentialId,
+        /// The fees already paid plus the maximum fee of the transaction.
+        requested: u64,
+        /// The spend limit of the grant.
+        spend_limit: u64,
+    },
+}
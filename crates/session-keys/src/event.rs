@@ -0,0 +1,36 @@
+use sov_modules_api::{CredentialId, Spec};
+
+/// Events emitted by the [`crate::SessionKeys`] module.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    Clone,
+)]
+#[serde(bound = "S::Address: serde::Serialize + serde::de::DeserializeOwned")]
+#[serde(rename_all = "snake_case")]
+pub enum Event<S: Spec> {
+    /// `granter` approved the session key `session`.
+    SessionGranted {
+        /// The account the session key acts for.
+        granter: S::Address,
+        /// The credential of the session key.
+        session: CredentialId,
+        /// The modules the session key can call.
+        modules: Vec<String>,
+        /// The spend limit of the session key.
+        spend_limit: u64,
+        /// The expiry height of the session key.
+        expires_at: u64,
+    },
+    /// `granter` revoked the session key `session`.
+    SessionRevoked {
+        /// The account the session key acted for.
+        granter: S::Address,
+        /// The credential of the session key.
+        session: CredentialId,
+    },
+}
@@ -0,0 +1,91 @@
+mod call;
+mod error;
+mod event;
+mod session;
+pub use call::{CallMessage, FORBIDDEN_MODULES};
+pub use error::SessionError;
+pub use event::Event;
+use serde::{Deserialize, Serialize};
+pub use session::ActiveSession;
+use sov_modules_api::{
+    CallResponse, Context, CredentialId, Error, GenesisState, Module, ModuleId, ModuleInfo,
+    ModuleRestApi, Spec, StateMap, TxState,
+};
+
+/// A permission granted by an account to a session key.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Serialize,
+    Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct SessionGrant {
+    /// Names of the runtime modules the session key can call, e.g. `example_module`.
+    pub modules: Vec<String>,
+    /// Maximum sum of the gas fees the session key can spend.
+    pub spend_limit: u64,
+    /// Sum of the gas fees paid by the transactions of the session key so far.
+    pub spent: u64,
+    /// The session key is rejected from this rollup height on.
+    pub expires_at: u64,
+}
+
+/// Holds the session keys granted by accounts.
+///
+/// A session key is a temporary key a user approves so that a frontend can send transactions
+/// on their behalf, restricted to some modules, up to a gas spend limit and until an expiry
+/// height. The module only stores the grants: the runtime authenticates session-signed
+/// transactions with [`SessionKeys::check_session`], resolves them to the granter's account
+/// and records the gas they paid with [`SessionKeys::record_spending`].
+#[derive(Clone, ModuleInfo, ModuleRestApi)]
+pub struct SessionKeys<S: Spec> {
+    /// Id of the module.
+    #[id]
+    pub id: ModuleId,
+
+    /// The grants, keyed by the granter and the credential of the session key. A grant only
+    /// lets the session key act for its own granter, so anyone can grant any credential.
+    /// Revoked grants are removed.
+    #[state]
+    pub grants: StateMap<(S::Address, CredentialId), SessionGrant>,
+}
+
+impl<S: Spec> Module for SessionKeys<S> {
+    type Spec = S;
+
+    type Config = ();
+
+    type CallMessage = call::CallMessage;
+
+    type Event = Event<S>;
+
+    fn genesis(
+        &self,
+        _config: &Self::Config,
+        _state: &mut impl GenesisState<S>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn call(
+        &self,
+        msg: Self::CallMessage,
+        context: &Context<Self::Spec>,
+        state: &mut impl TxState<S>,
+    ) -> Result<CallResponse, Error> {
+        match msg {
+            call::CallMessage::Grant {
+                session,
+                modules,
+                spend_limit,
+                expires_at,
+            } => Ok(self.grant(session, modules, spend_limit, expires_at, context, state)?),
+            call::CallMessage::Revoke { session } => Ok(self.revoke(session, context, state)?),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_modules_api::{CredentialId, Spec, StateReader, StateReaderAndWriter};
+use sov_state::namespaces::User;
+
+use crate::error::SessionError;
+use crate::SessionKeys;
+
+/// The session a transaction was authenticated with, added to the credentials of its sender
+/// so that the gas it pays can be recorded with [`SessionKeys::record_spending`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveSession<S: Spec> {
+    /// The account the session key acts for.
+    pub granter: S::Address,
+    /// The credential of the session key.
+    pub session: CredentialId,
+    /// The maximum fee of the transaction, reserved from the granter.
+    pub max_fee: u64,
+}
+
+impl<S: Spec> SessionKeys<S> {
+    /// Checks that `granter` allowed the session key `session` to send a transaction calling
+    /// `module` with a maximum fee of `max_fee` at rollup height `height`, the true slot number
+    /// of `chain_state`. Nothing is written: the gas is only counted against the spend limit
+    /// once paid.
+    pub fn check_session(
+        &self,
+        granter: &S::Address,
+        session: &CredentialId,
+        module: &str,
+        max_fee: u64,
+        height: u64,
+        state: &mut impl StateReader<User>,
+    ) -> anyhow::Result<()> {
+        let session = *session;
+        let Some(grant) = self
+            .grants
+            .get(&(granter.clone(), session), state)
+            .unwrap_infallible()
+        else {
+            return Err(SessionError::UnknownSession {
+                granter: granter.clone(),
+                session,
+            }
+            .into());
+        };
+
+        if height >= grant.expires_at {
+            return Err(SessionError::<S::Address>::Expired {
+                session,
+                expires_at: grant.expires_at,
+            }
+            .into());
+        }
+        if !grant.modules.iter().any(|allowed| allowed == module) {
+            return Err(SessionError::<S::Address>::OutOfScope {
+                session,
+                module: module.to_string(),
+            }
+            .into());
+        }
+
+        let requested = grant.spent.saturating_add(max_fee);
+        if requested > grant.spend_limit {
+            return Err(SessionError::<S::Address>::SpendLimitExceeded {
+                session,
+                requested,
+                spend_limit: grant.spend_limit,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Counts `amount`, a fee charged by a module to a transaction of `session`, against its
+    /// spend limit. Fails if the limit could be exceeded once the gas of the transaction, at
+    /// most its maximum fee, is paid as well. The spending is reverted with the transaction.
+    pub fn spend(
+        &self,
+        session: &ActiveSession<S>,
+        amount: u64,
+        state: &mut impl StateReaderAndWriter<User>,
+    ) -> anyhow::Result<()> {
+        let key = (session.granter.clone(), session.session);
+        let Some(mut grant) = self.grants.get(&key, state).unwrap_infallible() else {
+            return Err(SessionError::UnknownSession {
+                granter: session.granter.clone(),
+                session: session.session,
+            }
+            .into());
+        };
+
+        let spent = grant.spent.saturating_add(amount);
+        let requested = spent.saturating_add(session.max_fee);
+        if requested > grant.spend_limit {
+            return Err(SessionError::<S::Address>::SpendLimitExceeded {
+                session: session.session,
+                requested,
+                spend_limit: grant.spend_limit,
+            }
+            .into());
+        }
+
+        grant.spent = spent;
+        self.grants.set(&key, &grant, state).unwrap_infallible();
+        Ok(())
+    }
+
+    /// Counts `amount`, the gas fee paid by a transaction of `session`, against its spend
+    /// limit. Called once the gas of the transaction is settled, whether it succeeded or not.
+    pub fn record_spending(
+        &self,
+        session: &ActiveSession<S>,
+        amount: u64,
+        state: &mut impl StateReaderAndWriter<User>,
+    ) {
+        let key = (session.granter.clone(), session.session);
+        if let Some(mut grant) = self.grants.get(&key, state).unwrap_infallible() {
+            grant.spent = grant.spent.saturating_add(amount);
+            self.grants.set(&key, &grant, state).unwrap_infallible();
+        }
+    }
+}
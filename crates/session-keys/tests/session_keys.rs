@@ -0,0 +1,201 @@
+use session_keys::{ActiveSession, CallMessage, Event, SessionKeys};
+use sov_modules_api::CredentialId;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::runtime::TestRunner;
+use sov_test_utils::{generate_optimistic_runtime, AsUser, TestUser, TransactionTestCase};
+
+generate_optimistic_runtime!(SessionKeysRuntime <= session_keys: SessionKeys<S>);
+
+type S = sov_test_utils::TestSpec;
+
+const SESSION: CredentialId = CredentialId([7; 32]);
+
+fn setup() -> (
+    TestUser<S>,
+    TestUser<S>,
+    TestRunner<SessionKeysRuntime<S>, S>,
+) {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(2);
+
+    let granter = genesis_config.additional_accounts[0].clone();
+    let other = genesis_config.additional_accounts[1].clone();
+
+    let genesis = GenesisConfig::from_minimal_config(genesis_config.into(), ());
+
+    let runner =
+        TestRunner::new_with_genesis(genesis.into_genesis_params(), SessionKeysRuntime::default());
+
+    (granter, other, runner)
+}
+
+fn grant(modules: &[&str], spend_limit: u64, expires_at: u64) -> CallMessage {
+    CallMessage::Grant {
+        session: SESSION,
+        modules: modules.iter().map(|module| module.to_string()).collect(),
+        spend_limit,
+        expires_at,
+    }
+}
+
+#[test]
+fn test_grant_and_check_session() {
+    let (granter, other, mut runner) = setup();
+    let granter_address = granter.address();
+
+    runner.execute_transaction(TransactionTestCase {
+        input: granter.create_plain_message::<SessionKeys<S>>(grant(&["example_module"], 100, 10)),
+        assert: Box::new(move |result, _state| {
+            assert!(result.tx_receipt.is_successful());
+            assert_eq!(
+                result.events,
+                vec![SessionKeysRuntimeEvent::SessionKeys(
+                    Event::SessionGranted {
+                        granter: granter_address,
+                        session: SESSION,
+                        modules: vec!["example_module".to_string()],
+                        spend_limit: 100,
+                        expires_at: 10,
+                    }
+                )]
+            );
+        }),
+    });
+
+    // Another account can grant the same credential, which only lets it act for that account.
+    runner.execute_transaction(TransactionTestCase {
+        input: other.create_plain_message::<SessionKeys<S>>(grant(&["governance"], 100, 10)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    // But the same account cannot grant it twice.
+    runner.execute_transaction(TransactionTestCase {
+        input: granter.create_plain_message::<SessionKeys<S>>(grant(&["example_module"], 100, 10)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    let granter_address = granter.address();
+    let other_address = other.address();
+    runner.query_state(|state| {
+        let session_keys = SessionKeys::<S>::default();
+
+        assert!(session_keys
+            .check_session(&granter_address, &SESSION, "example_module", 100, 5, state)
+            .is_ok());
+        // Out of the scope of the granter, even though `other` allowed it.
+        assert!(session_keys
+            .check_session(&granter_address, &SESSION, "governance", 10, 5, state)
+            .is_err());
+        assert!(session_keys
+            .check_session(&other_address, &SESSION, "governance", 10, 5, state)
+            .is_ok());
+        // Above the spend limit.
+        assert!(session_keys
+            .check_session(&granter_address, &SESSION, "example_module", 101, 5, state)
+            .is_err());
+        // Expired.
+        assert!(session_keys
+            .check_session(&granter_address, &SESSION, "example_module", 0, 10, state)
+            .is_err());
+        // Unknown.
+        assert!(session_keys
+            .check_session(
+                &granter_address,
+                &CredentialId([8; 32]),
+                "example_module",
+                0,
+                5,
+                state
+            )
+            .is_err());
+
+        // Only the gas actually paid counts against the limit.
+        let session = ActiveSession {
+            granter: granter_address.clone(),
+            session: SESSION,
+            max_fee: 100,
+        };
+        session_keys.record_spending(&session, 60, state);
+        assert!(session_keys
+            .check_session(&granter_address, &SESSION, "example_module", 41, 5, state)
+            .is_err());
+        assert!(session_keys
+            .check_session(&granter_address, &SESSION, "example_module", 40, 5, state)
+            .is_ok());
+        // The grant of `other` is unaffected.
+        assert!(session_keys
+            .check_session(&other_address, &SESSION, "governance", 100, 5, state)
+            .is_ok());
+    });
+}
+
+#[test]
+fn test_revoke() {
+    let (granter, other, mut runner) = setup();
+
+    runner.execute_transaction(TransactionTestCase {
+        input: granter.create_plain_message::<SessionKeys<S>>(grant(&["example_module"], 100, 10)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    // Only the granter can revoke its grant.
+    runner.execute_transaction(TransactionTestCase {
+        input: other
+            .create_plain_message::<SessionKeys<S>>(CallMessage::Revoke { session: SESSION }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_reverted());
+        }),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: granter
+            .create_plain_message::<SessionKeys<S>>(CallMessage::Revoke { session: SESSION }),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+
+    let granter_address = granter.address();
+    runner.query_state(|state| {
+        assert!(SessionKeys::<S>::default()
+            .check_session(&granter_address, &SESSION, "example_module", 0, 5, state)
+            .is_err());
+    });
+
+    // Revoked grants are removed, the session key can be granted again.
+    runner.execute_transaction(TransactionTestCase {
+        input: granter.create_plain_message::<SessionKeys<S>>(grant(&["example_module"], 100, 10)),
+        assert: Box::new(|result, _state| {
+            assert!(result.tx_receipt.is_successful());
+        }),
+    });
+}
+
+#[test]
+fn test_invalid_grants() {
+    let (granter, _, mut runner) = setup();
+
+    for message in [
+        grant(&[], 100, 10),
+        grant(&["session_keys"], 100, 10),
+        // Session keys cannot move the granter's funds.
+        grant(&["example_module", "bank"], 100, 10),
+        grant(&["accounts"], 100, 10),
+        // Nor act as the governor.
+        grant(&["governance"], 100, 10),
+        grant(&["example_module"], 100, 0),
+    ] {
+        runner.execute_transaction(TransactionTestCase {
+            input: granter.create_plain_message::<SessionKeys<S>>(message),
+            assert: Box::new(|result, _state| {
+                assert!(result.tx_receipt.is_reverted());
+            }),
+        });
+    }
+}
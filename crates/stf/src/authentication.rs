@@ -1,18 +1,35 @@
-//! The stf-rollup supports `sov-module` authenticator. To support other authentication schemes,
-//! you can check out how we support `EVM` authenticator here:
-//! https://github.com/Sovereign-Labs/sovereign-sdk-wip/blob/146d5c2c5fa07ab7bb59ba6b2e64690ac9b63830/examples/demo-rollup/stf/src/authentication.rs#L29-L32
+//! The stf-rollup supports several authenticators:
+//! - `Auth::Mod`: borsh-encoded `sov-modules` transactions, signed with the rollup's own scheme.
+//! - `Auth::Evm`: RLP-encoded Ethereum transactions signed with secp256k1, dispatched to the
+//!   `evm` module. The signer is mapped to a rollup address through the credential registered
+//!   in the `accounts` module.
+//! - `Auth::Multisig`: a `sov-modules` transaction co-signed by M of the N keys of a multisig,
+//!   authorized with the multisig's credential. See [`crate::multisig`].
+//! - `Auth::Session`: a `sov-modules` transaction signed by a session key on behalf of the
+//!   account named in the envelope, within the scope of the grant recorded by that account in
+//!   the `session_keys` module. Outside of this envelope, a session key is a regular credential.
+//! - `Auth::Sponsored`: a `sov-modules` transaction whose gas is paid by a sponsor registered
+//!   in the `paymaster` module. See [`crate::capabilities`].
+//!
+//! Whatever the authenticator, calls to the modules paused by the `governance` module are
+//! rejected here, before they are executed.
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use sov_modules_api::capabilities::FatalError;
 use std::marker::PhantomData;
 
+use crate::multisig::MultisigProof;
 use crate::runtime::{Runtime, RuntimeCall};
+use governance::GOVERNANCE_MODULE;
+use session_keys::ActiveSession;
 
 use sov_modules_api::capabilities::{
     AuthenticationError, AuthenticationOutput, AuthorizationData, UnregisteredAuthenticationError,
 };
+use sov_modules_api::macros::config_value;
 use sov_modules_api::runtime::capabilities::TransactionAuthenticator;
-use sov_modules_api::{DaSpec, DispatchCall, PreExecWorkingSet, RawTx, Spec};
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{DaSpec, DispatchCall, GasArray, GasMeter, PreExecWorkingSet, RawTx, Spec};
 
 impl<S: Spec> TransactionAuthenticator<S> for Runtime<S> {
     type Decodable = <Self as DispatchCall>::Decodable;
@@ -30,11 +47,30 @@ impl<S: Spec> TransactionAuthenticator<S> for Runtime<S> {
         AuthenticationOutput<S, Self::Decodable, Self::AuthorizationData>,
         AuthenticationError,
     > {
-        match input {
+        let (tx_and_raw_hash, auth_data, runtime_call) = match input {
             Auth::Mod(tx) => {
-                sov_modules_api::capabilities::authenticate::<S, Self>(tx, pre_exec_ws)
+                sov_modules_api::capabilities::authenticate::<S, Self>(tx, pre_exec_ws)?
             }
+            Auth::Evm(tx) => {
+                let (tx_and_raw_hash, auth_data, call) =
+                    sov_evm::authenticate::<S>(tx, pre_exec_ws)?;
+                (tx_and_raw_hash, auth_data, RuntimeCall::Evm(call))
+            }
+            Auth::Multisig { tx, proof } => self.authenticate_multisig(tx, proof, pre_exec_ws)?,
+            Auth::Session { tx, granter } => self.authenticate_session(tx, granter, pre_exec_ws)?,
+            Auth::Sponsored { tx, sponsor } => {
+                self.authenticate_sponsored(tx, sponsor, pre_exec_ws)?
+            }
+        };
+
+        if let Err(err) = self.ensure_module_not_paused(&runtime_call, pre_exec_ws) {
+            return Err(AuthenticationError::FatalError(
+                err,
+                tx_and_raw_hash.raw_tx_hash,
+            ));
         }
+
+        Ok((tx_and_raw_hash, auth_data, runtime_call))
     }
 
     fn authenticate_unregistered(
@@ -45,41 +81,358 @@ impl<S: Spec> TransactionAuthenticator<S> for Runtime<S> {
         AuthenticationOutput<S, Self::Decodable, Self::AuthorizationData>,
         UnregisteredAuthenticationError,
     > {
-        let Auth::Mod(contents) = raw_tx;
-
-        let (tx_and_raw_hash, auth_data, runtime_call) =
-            sov_modules_api::capabilities::authenticate::<S, Runtime<S>>(contents, pre_exec_ws)
-                .map_err(|e| match e {
-                    AuthenticationError::FatalError(err, hash) => {
-                        UnregisteredAuthenticationError::FatalError(err, hash)
-                    }
-                    AuthenticationError::OutOfGas(err) => {
-                        UnregisteredAuthenticationError::OutOfGas(err)
-                    }
-                })?;
-
-        match &runtime_call {
-            RuntimeCall::SequencerRegistry(sov_sequencer_registry::CallMessage::Register {
-                ..
-            }) => Ok((tx_and_raw_hash, auth_data, runtime_call)),
-            _ => Err(UnregisteredAuthenticationError::FatalError(
-                FatalError::Other(
-                    "The runtime call included in the transaction was invalid.".to_string(),
-                ),
+        let (tx_and_raw_hash, auth_data, runtime_call) = match raw_tx {
+            Auth::Mod(contents) => {
+                sov_modules_api::capabilities::authenticate::<S, Self>(contents, pre_exec_ws)
+                    .map_err(into_unregistered)?
+            }
+            Auth::Multisig { tx, proof } => self
+                .authenticate_multisig(tx, proof, pre_exec_ws)
+                .map_err(into_unregistered)?,
+            // Unregistered senders can only submit the calls allowed by governance, which must
+            // be authorized by their own signature and paid for by themselves.
+            Auth::Session { tx, .. } | Auth::Sponsored { tx, .. } => {
+                let (tx_and_raw_hash, _, _) =
+                    sov_modules_api::capabilities::authenticate::<S, Self>(tx, pre_exec_ws)
+                        .map_err(into_unregistered)?;
+                return Err(UnregisteredAuthenticationError::FatalError(
+                    FatalError::Other(
+                        "Session keys and sponsors cannot be used by unregistered sequencers"
+                            .to_string(),
+                    ),
+                    tx_and_raw_hash.raw_tx_hash,
+                ));
+            }
+            Auth::Evm(tx) => {
+                let (tx_and_raw_hash, _, _) =
+                    sov_evm::authenticate::<S>(tx, pre_exec_ws).map_err(into_unregistered)?;
+                return Err(UnregisteredAuthenticationError::FatalError(
+                    FatalError::Other(
+                        "EVM transactions from unregistered sequencers are not supported"
+                            .to_string(),
+                    ),
+                    tx_and_raw_hash.raw_tx_hash,
+                ));
+            }
+        };
+
+        if let Err(err) = self
+            .ensure_unregistered_call_allowed(&runtime_call, pre_exec_ws)
+            .and_then(|()| self.ensure_module_not_paused(&runtime_call, pre_exec_ws))
+        {
+            return Err(UnregisteredAuthenticationError::FatalError(
+                err,
                 tx_and_raw_hash.raw_tx_hash,
-            ))?,
+            ));
         }
+
+        Ok((tx_and_raw_hash, auth_data, runtime_call))
     }
     fn add_standard_auth(tx: RawTx) -> Self::Input {
         Auth::Mod(tx.data)
     }
 }
 
+/// Converts the errors of the registered-sender authenticators into the ones expected by
+/// [`TransactionAuthenticator::authenticate_unregistered`].
+fn into_unregistered(e: AuthenticationError) -> UnregisteredAuthenticationError {
+    match e {
+        AuthenticationError::FatalError(err, hash) => {
+            UnregisteredAuthenticationError::FatalError(err, hash)
+        }
+        AuthenticationError::OutOfGas(err) => UnregisteredAuthenticationError::OutOfGas(err),
+    }
+}
+
+impl<S: Spec> Runtime<S> {
+    /// Authenticates the submitter's transaction `tx` like [`Auth::Mod`], then checks the
+    /// co-signatures of `proof` and swaps the submitter's credential for the multisig's. The
+    /// signature verifications are charged before they run.
+    fn authenticate_multisig(
+        &self,
+        tx: &[u8],
+        proof: &[u8],
+        pre_exec_ws: &mut PreExecWorkingSet<S>,
+    ) -> Result<
+        AuthenticationOutput<S, <Self as DispatchCall>::Decodable, AuthorizationData<S>>,
+        AuthenticationError,
+    > {
+        let (tx_and_raw_hash, mut auth_data, runtime_call) =
+            sov_modules_api::capabilities::authenticate::<S, Self>(tx, pre_exec_ws)?;
+
+        let proof = MultisigProof::<S>::try_from_slice(proof).map_err(|e| {
+            AuthenticationError::FatalError(
+                FatalError::Other(format!("Invalid multisig proof: {e}")),
+                tx_and_raw_hash.raw_tx_hash,
+            )
+        })?;
+
+        let gas_per_signature = S::Gas::from_slice(&config_value!(
+            "DEFAULT_FIXED_GAS_TO_CHARGE_PER_SIGNATURE_VERIFICATION"
+        ));
+        for _ in &proof.co_signatures {
+            pre_exec_ws
+                .charge_gas(&gas_per_signature)
+                .map_err(|e| AuthenticationError::OutOfGas(e.to_string()))?;
+        }
+
+        match proof.verify(tx, &auth_data.credential_id) {
+            Ok(credential_id) => {
+                auth_data.credential_id = credential_id;
+                auth_data.default_address = credential_id.into();
+                Ok((tx_and_raw_hash, auth_data, runtime_call))
+            }
+            Err(e) => Err(AuthenticationError::FatalError(
+                FatalError::Other(format!("Multisig authentication failed: {e}")),
+                tx_and_raw_hash.raw_tx_hash,
+            )),
+        }
+    }
+
+    /// Authenticates a transaction signed by a session key and resolves it to `granter`, a
+    /// borsh-encoded address, provided `granter` allowed the session key to make the call. The
+    /// session is added to the credentials so that the gas paid counts against its limit.
+    fn authenticate_session(
+        &self,
+        tx: &[u8],
+        granter: &[u8],
+        pre_exec_ws: &mut PreExecWorkingSet<S>,
+    ) -> Result<
+        AuthenticationOutput<S, <Self as DispatchCall>::Decodable, AuthorizationData<S>>,
+        AuthenticationError,
+    > {
+        let (tx_and_raw_hash, mut auth_data, runtime_call) =
+            sov_modules_api::capabilities::authenticate::<S, Self>(tx, pre_exec_ws)?;
+
+        let session = S::Address::try_from_slice(granter)
+            .map_err(anyhow::Error::from)
+            .and_then(|granter| {
+                let max_fee = Transaction::<S>::try_from_slice(tx)?.max_fee();
+                let height = self.rollup_height(pre_exec_ws);
+                self.session_keys.check_session(
+                    &granter,
+                    &auth_data.credential_id,
+                    runtime_call.module_name(),
+                    max_fee,
+                    height,
+                    pre_exec_ws,
+                )?;
+                Ok(ActiveSession {
+                    granter,
+                    session: auth_data.credential_id,
+                    max_fee,
+                })
+            });
+
+        match session {
+            Ok(session) => {
+                auth_data.default_address = session.granter.clone();
+                auth_data.credentials.insert(session);
+                Ok((tx_and_raw_hash, auth_data, runtime_call))
+            }
+            Err(e) => Err(AuthenticationError::FatalError(
+                FatalError::Other(format!("Session key authentication failed: {e}")),
+                tx_and_raw_hash.raw_tx_hash,
+            )),
+        }
+    }
+
+    /// Authenticates a regular transaction whose gas is to be paid by `sponsor`, a
+    /// borsh-encoded address, provided the policy of the sponsor covers the call. The
+    /// sponsorship is added to the credentials; whether it covers the sender is checked when
+    /// the gas is reserved, once the sender is resolved.
+    fn authenticate_sponsored(
+        &self,
+        tx: &[u8],
+        sponsor: &[u8],
+        pre_exec_ws: &mut PreExecWorkingSet<S>,
+    ) -> Result<
+        AuthenticationOutput<S, <Self as DispatchCall>::Decodable, AuthorizationData<S>>,
+        AuthenticationError,
+    > {
+        let (tx_and_raw_hash, mut auth_data, runtime_call) =
+            sov_modules_api::capabilities::authenticate::<S, Self>(tx, pre_exec_ws)?;
+
+        let sponsorship = S::Address::try_from_slice(sponsor)
+            .map_err(anyhow::Error::from)
+            .and_then(|sponsor| {
+                let max_fee = Transaction::<S>::try_from_slice(tx)?.max_fee();
+                self.paymaster.request_sponsorship(
+                    sponsor,
+                    runtime_call.module_name(),
+                    max_fee,
+                    pre_exec_ws,
+                )
+            });
+
+        match sponsorship {
+            Ok(sponsorship) => {
+                auth_data.credentials.insert(sponsorship);
+                Ok((tx_and_raw_hash, auth_data, runtime_call))
+            }
+            Err(e) => Err(AuthenticationError::FatalError(
+                FatalError::Other(format!("Sponsorship rejected: {e}")),
+                tx_and_raw_hash.raw_tx_hash,
+            )),
+        }
+    }
+
+    /// Rejects calls to the modules paused by governance, before they are executed. Calls to
+    /// the `governance` module itself always go through, so that paused modules can be
+    /// unpaused.
+    fn ensure_module_not_paused(
+        &self,
+        runtime_call: &RuntimeCall<S>,
+        pre_exec_ws: &mut PreExecWorkingSet<S>,
+    ) -> Result<(), FatalError> {
+        let module = runtime_call.module_name();
+        if module == GOVERNANCE_MODULE {
+            return Ok(());
+        }
+
+        let paused = self
+            .governance
+            .is_paused(module, pre_exec_ws)
+            .map_err(|e| {
+                FatalError::Other(format!(
+                    "Failed to check whether module `{module}` is paused: {e:?}"
+                ))
+            })?;
+        if paused {
+            return Err(FatalError::Other(format!(
+                "Module `{module}` is paused by governance, its calls are rejected"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects calls that governance does not allow unregistered sequencers to submit.
+    fn ensure_unregistered_call_allowed(
+        &self,
+        runtime_call: &RuntimeCall<S>,
+        pre_exec_ws: &mut PreExecWorkingSet<S>,
+    ) -> Result<(), FatalError> {
+        let module = runtime_call.module_name();
+        let call = runtime_call.call_name();
+        let allowed = self
+            .governance
+            .is_unregistered_call_allowed(module, call, pre_exec_ws)
+            .map_err(|e| {
+                FatalError::Other(format!(
+                    "Failed to check whether unregistered sequencers can call `{module}`: {e:?}"
+                ))
+            })?;
+
+        if !allowed {
+            return Err(FatalError::Other(format!(
+                "Call `{}` of module `{module}` cannot be sent by unregistered sequencers",
+                call.unwrap_or("<unnamed>")
+            )));
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 pub enum Auth {
     Mod(Vec<u8>),
+    /// An RLP-encoded, secp256k1-signed Ethereum transaction. Declared after `Mod` so that
+    /// the borsh encoding of existing transactions does not change.
+    Evm(Vec<u8>),
+    /// A `sov-modules` transaction sent from a multisig.
+    Multisig {
+        /// The borsh-encoded transaction, signed by one of the keys of the multisig.
+        tx: Vec<u8>,
+        /// The borsh-encoded [`MultisigProof`] holding the other signatures.
+        proof: Vec<u8>,
+    },
+    /// A `sov-modules` transaction signed by a session key.
+    Session {
+        /// The borsh-encoded transaction, signed by the session key.
+        tx: Vec<u8>,
+        /// The borsh-encoded address of the account that granted the session key.
+        granter: Vec<u8>,
+    },
+    /// A `sov-modules` transaction whose gas is paid by a sponsor.
+    Sponsored {
+        /// The borsh-encoded transaction, signed by the user.
+        tx: Vec<u8>,
+        /// The borsh-encoded address of the sponsor.
+        sponsor: Vec<u8>,
+    },
+}
+
+impl<S: Spec> sov_evm::EthereumAuthenticator<S> for Runtime<S> {
+    fn add_ethereum_auth(
+        tx: sov_evm::RlpEvmTransaction,
+    ) -> <Self as TransactionAuthenticator<S>>::Input {
+        Auth::Evm(tx.rlp)
+    }
 }
 
 pub struct ModAuth<S: Spec, Da: DaSpec> {
     _phantom: PhantomData<(S, Da)>,
 }
+
+/// Wraps multisig transactions into the runtime's [`Auth`] envelope.
+pub struct MultisigAuth<S: Spec, Da: DaSpec> {
+    _phantom: PhantomData<(S, Da)>,
+}
+
+impl<S: Spec, Da: DaSpec> MultisigAuth<S, Da> {
+    /// Encodes the submitter's transaction `tx` together with `proof`.
+    pub fn encode(tx: Vec<u8>, proof: &MultisigProof<S>) -> Result<RawTx, anyhow::Error> {
+        let data = borsh::to_vec(&Auth::Multisig {
+            tx,
+            proof: borsh::to_vec(proof)?,
+        })?;
+        Ok(RawTx { data })
+    }
+}
+
+/// Wraps transactions signed by session keys into the runtime's [`Auth`] envelope.
+pub struct SessionAuth<S: Spec, Da: DaSpec> {
+    _phantom: PhantomData<(S, Da)>,
+}
+
+impl<S: Spec, Da: DaSpec> SessionAuth<S, Da> {
+    /// Encodes `tx`, signed by a session key granted by `granter`.
+    pub fn encode(tx: Vec<u8>, granter: &S::Address) -> Result<RawTx, anyhow::Error> {
+        let data = borsh::to_vec(&Auth::Session {
+            tx,
+            granter: borsh::to_vec(granter)?,
+        })?;
+        Ok(RawTx { data })
+    }
+}
+
+/// Wraps sponsored transactions into the runtime's [`Auth`] envelope.
+pub struct SponsoredAuth<S: Spec, Da: DaSpec> {
+    _phantom: PhantomData<(S, Da)>,
+}
+
+impl<S: Spec, Da: DaSpec> SponsoredAuth<S, Da> {
+    /// Encodes `tx`, whose gas is to be paid by `sponsor`.
+    pub fn encode(tx: Vec<u8>, sponsor: &S::Address) -> Result<RawTx, anyhow::Error> {
+        let data = borsh::to_vec(&Auth::Sponsored {
+            tx,
+            sponsor: borsh::to_vec(sponsor)?,
+        })?;
+        Ok(RawTx { data })
+    }
+}
+
+/// Wraps RLP-encoded Ethereum transactions into the runtime's [`Auth`] envelope.
+pub struct EvmAuth<S: Spec, Da: DaSpec> {
+    _phantom: PhantomData<(S, Da)>,
+}
+
+impl<S: Spec, Da: DaSpec> EvmAuth<S, Da> {
+    /// Encodes `rlp_tx` so that it can be submitted in a batch.
+    pub fn encode(rlp_tx: Vec<u8>) -> Result<RawTx, anyhow::Error> {
+        let data = borsh::to_vec(&Auth::Evm(rlp_tx))?;
+        Ok(RawTx { data })
+    }
+}
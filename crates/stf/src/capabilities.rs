@@ -0,0 +1,225 @@
+//! The capabilities of the runtime: the standard ones, except that the gas of sponsored
+//! transactions is reserved from, and refunded to, their sponsor, and that the gas paid by
+//! session keys is counted against the spend limit of their grant. Session transactions run
+//! as their granter without touching the credentials known to `accounts`.
+use anyhow::Context as _;
+use paymaster::Sponsorship;
+use session_keys::ActiveSession;
+use sov_capabilities::StandardProvenRollupCapabilities as StandardCapabilities;
+use sov_modules_api::capabilities::{
+    AuthorizationData, GasEnforcer, ProofProcessor, RuntimeAuthorization, SequencerAuthorization,
+};
+use sov_modules_api::transaction::{AuthenticatedTransactionData, TransactionConsumption};
+use sov_modules_api::{
+    Context, DaSpec, Gas, PreExecWorkingSet, Spec, StateCheckpoint, TxScratchpad, WorkingSet,
+};
+
+/// The [`StandardCapabilities`], with gas payments routed through the `paymaster` module.
+pub struct RollupCapabilities<'a, S: Spec> {
+    /// The capabilities every call is delegated to.
+    pub standard: StandardCapabilities<'a, S>,
+    /// The module deciding who pays for the gas of a transaction.
+    pub paymaster: &'a paymaster::Paymaster<S>,
+    /// The module holding the spend limits of the session keys.
+    pub session_keys: &'a session_keys::SessionKeys<S>,
+}
+
+/// `context` with the sender replaced by the account paying for the gas.
+fn payer_context<S: Spec>(context: &Context<S>, payer: S::Address) -> Context<S> {
+    Context::new(
+        payer,
+        context.get_sender_credentials().clone(),
+        context.sequencer().clone(),
+        context.visible_slot_number(),
+    )
+}
+
+impl<'a, S: Spec> GasEnforcer<S> for RollupCapabilities<'a, S> {
+    fn try_reserve_gas(
+        &self,
+        tx: &AuthenticatedTransactionData<S>,
+        context: &Context<S>,
+        gas_price: &<S::Gas as Gas>::Price,
+        mut state: PreExecWorkingSet<S>,
+    ) -> Result<WorkingSet<S>, (PreExecWorkingSet<S>, anyhow::Error)> {
+        let Some(sponsorship) = context.get_sender_credentials().get::<Sponsorship<S>>() else {
+            return self.standard.try_reserve_gas(tx, context, gas_price, state);
+        };
+
+        match self
+            .paymaster
+            .check_sender(sponsorship, context.sender(), &mut state)
+        {
+            Ok(()) => self.standard.try_reserve_gas(
+                tx,
+                &payer_context(context, sponsorship.sponsor.clone()),
+                gas_price,
+                state,
+            ),
+            Err(e) => Err((state, e)),
+        }
+    }
+
+    fn allocate_consumed_gas(
+        &self,
+        tx_consumption: &TransactionConsumption<S::Gas>,
+        state: &mut TxScratchpad<S::Storage>,
+    ) {
+        self.standard.allocate_consumed_gas(tx_consumption, state);
+    }
+
+    fn refund_remaining_gas(
+        &self,
+        tx_consumption: &TransactionConsumption<S::Gas>,
+        context: &Context<S>,
+        state: &mut TxScratchpad<S::Storage>,
+    ) {
+        // The gas is settled whether the transaction succeeded or not, which is also when the
+        // session key or the sponsor spent it.
+        let credentials = context.get_sender_credentials();
+        if let Some(session) = credentials.get::<ActiveSession<S>>() {
+            let paid = session
+                .max_fee
+                .saturating_sub(tx_consumption.remaining_funds());
+            self.session_keys.record_spending(session, paid, state);
+        }
+
+        match credentials.get::<Sponsorship<S>>() {
+            Some(sponsorship) => {
+                let paid = sponsorship
+                    .max_fee
+                    .saturating_sub(tx_consumption.remaining_funds());
+                self.paymaster.record_spending(sponsorship, paid, state);
+                self.standard.refund_remaining_gas(
+                    tx_consumption,
+                    &payer_context(context, sponsorship.sponsor.clone()),
+                    state,
+                );
+            }
+            None => self
+                .standard
+                .refund_remaining_gas(tx_consumption, context, state),
+        }
+    }
+}
+
+impl<'a, S: Spec> SequencerAuthorization<S> for RollupCapabilities<'a, S> {
+    type SequencerStakeMeter =
+        <StandardCapabilities<'a, S> as SequencerAuthorization<S>>::SequencerStakeMeter;
+
+    fn authorize_sequencer(
+        &self,
+        sequencer: &<S::Da as DaSpec>::Address,
+        base_fee_per_gas: &<S::Gas as Gas>::Price,
+        state: &mut PreExecWorkingSet<S>,
+    ) -> Result<Self::SequencerStakeMeter, anyhow::Error> {
+        self.standard
+            .authorize_sequencer(sequencer, base_fee_per_gas, state)
+    }
+
+    fn penalize_sequencer(
+        &self,
+        sequencer: &<S::Da as DaSpec>::Address,
+        reason: impl std::fmt::Display,
+        state: &mut PreExecWorkingSet<S>,
+    ) {
+        self.standard.penalize_sequencer(sequencer, reason, state);
+    }
+}
+
+impl<'a, S: Spec> RuntimeAuthorization<S> for RollupCapabilities<'a, S> {
+    fn resolve_context(
+        &self,
+        auth_data: &AuthorizationData<S>,
+        sequencer: &<S::Da as DaSpec>::Address,
+        height: u64,
+        state: &mut PreExecWorkingSet<S>,
+    ) -> Result<Context<S>, anyhow::Error> {
+        // A session key acts for its granter only within the session. The standard resolution
+        // would run it as the account its credential maps to, and map a new credential to the
+        // granter for good, so plain transactions of the key would then act as the granter.
+        let Some(session) = auth_data.credentials.get::<ActiveSession<S>>() else {
+            return self
+                .standard
+                .resolve_context(auth_data, sequencer, height, state);
+        };
+
+        let sequencer = self
+            .standard
+            .sequencer_registry
+            .resolve_da_address(sequencer, state)?
+            .context("The sequencer is no longer registered")?;
+        Ok(Context::new(
+            session.granter.clone(),
+            auth_data.credentials.clone(),
+            sequencer,
+            height,
+        ))
+    }
+
+    fn resolve_unregistered_context(
+        &self,
+        auth_data: &AuthorizationData<S>,
+        height: u64,
+        state: &mut PreExecWorkingSet<S>,
+    ) -> Result<Context<S>, anyhow::Error> {
+        self.standard
+            .resolve_unregistered_context(auth_data, height, state)
+    }
+
+    fn check_uniqueness(
+        &self,
+        auth_data: &AuthorizationData<S>,
+        context: &Context<S>,
+        state: &mut PreExecWorkingSet<S>,
+    ) -> Result<(), anyhow::Error> {
+        self.standard.check_uniqueness(auth_data, context, state)
+    }
+
+    fn mark_tx_attempted(
+        &self,
+        auth_data: &AuthorizationData<S>,
+        sequencer: &<S::Da as DaSpec>::Address,
+        state: &mut StateCheckpoint<S::Storage>,
+    ) {
+        self.standard.mark_tx_attempted(auth_data, sequencer, state);
+    }
+}
+
+impl<'a, S: Spec> ProofProcessor<S> for RollupCapabilities<'a, S> {
+    fn process_proof(
+        &self,
+        proof: &[u8],
+        prover_address: &S::Address,
+        state: &mut WorkingSet<S>,
+    ) -> Result<
+        (
+            sov_modules_api::SerializedAggregatedProof,
+            sov_modules_api::AggregatedProofPublicData,
+        ),
+        anyhow::Error,
+    > {
+        self.standard.process_proof(proof, prover_address, state)
+    }
+
+    fn process_attestation(
+        &self,
+        attestation: &[u8],
+        attester_address: &S::Address,
+        state: &mut WorkingSet<S>,
+    ) -> Result<sov_modules_api::Attestation<S>, anyhow::Error> {
+        self.standard
+            .process_attestation(attestation, attester_address, state)
+    }
+
+    fn process_challenge(
+        &self,
+        challenge: &[u8],
+        rollup_height: u64,
+        challenger_address: &S::Address,
+        state: &mut WorkingSet<S>,
+    ) -> Result<(), anyhow::Error> {
+        self.standard
+            .process_challenge(challenge, rollup_height, challenger_address, state)
+    }
+}
@@ -1,18 +1,32 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
-use example_module::ExampleModuleConfig;
+use anyhow::{bail, Context};
+use governance::GovernanceConfig;
 use serde::de::DeserializeOwned;
-use sov_accounts::AccountConfig;
-use sov_attester_incentives::AttesterIncentivesConfig;
-use sov_bank::BankConfig;
-use sov_chain_state::ChainStateConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sov_modules_api::Spec;
 use sov_modules_stf_blueprint::Runtime as RuntimeTrait;
-use sov_prover_incentives::ProverIncentivesConfig;
-use sov_sequencer_registry::SequencerConfig;
 
 use super::GenesisConfig;
-use crate::Runtime;
+use crate::genesis_validation::validate_genesis;
+use crate::{Runtime, RuntimeCall};
+
+/// Names of the modules configured from genesis files, as used in single-file genesis
+/// documents.
+pub const GENESIS_MODULES: [&str; 10] = [
+    "accounts",
+    "bank",
+    "sequencer_registry",
+    "attester_incentives",
+    "prover_incentives",
+    "example_module",
+    "governance",
+    "evm",
+    "paymaster",
+    "chain_state",
+];
 
 /// Paths to genesis files.
 pub struct GenesisPaths {
@@ -28,6 +42,14 @@ pub struct GenesisPaths {
     pub prover_incentives_genesis_path: PathBuf,
     /// Chain State genesis path.
     pub chain_state_genesis_path: PathBuf,
+    /// Example Module genesis path.
+    pub example_module_genesis_path: PathBuf,
+    /// Governance genesis path.
+    pub governance_genesis_path: PathBuf,
+    /// EVM genesis path.
+    pub evm_genesis_path: PathBuf,
+    /// Paymaster genesis path.
+    pub paymaster_genesis_path: PathBuf,
 }
 
 impl GenesisPaths {
@@ -44,49 +66,208 @@ impl GenesisPaths {
             attester_incentives_genesis_path: dir.as_ref().join("attester_incentives.json"),
             prover_incentives_genesis_path: dir.as_ref().join("prover_incentives.json"),
             chain_state_genesis_path: dir.as_ref().join("chain_state.json"),
+            example_module_genesis_path: dir.as_ref().join("example_module.json"),
+            governance_genesis_path: dir.as_ref().join("governance.json"),
+            evm_genesis_path: dir.as_ref().join("evm.json"),
+            paymaster_genesis_path: dir.as_ref().join("paymaster.json"),
         }
     }
+
+    /// The genesis file of every module in [`GENESIS_MODULES`], by module name.
+    pub fn modules(&self) -> [(&'static str, &Path); 10] {
+        [
+            ("accounts", &self.accounts_genesis_path),
+            ("bank", &self.bank_genesis_path),
+            ("sequencer_registry", &self.sequencer_genesis_path),
+            (
+                "attester_incentives",
+                &self.attester_incentives_genesis_path,
+            ),
+            ("prover_incentives", &self.prover_incentives_genesis_path),
+            ("example_module", &self.example_module_genesis_path),
+            ("governance", &self.governance_genesis_path),
+            ("evm", &self.evm_genesis_path),
+            ("paymaster", &self.paymaster_genesis_path),
+            ("chain_state", &self.chain_state_genesis_path),
+        ]
+    }
 }
 
-/// Creates a new [`GenesisConfig`] from the files contained in the given
-/// directory.
-pub fn create_genesis_config<S: Spec>(
-    genesis_paths: &GenesisPaths,
-) -> anyhow::Result<<Runtime<S> as RuntimeTrait<S>>::GenesisConfig> {
-    let accounts_config: AccountConfig<S> =
-        read_genesis_json(&genesis_paths.accounts_genesis_path)?;
-    let bank_config: BankConfig<S> = read_genesis_json(&genesis_paths.bank_genesis_path)?;
-    let sequencer_registry_config: SequencerConfig<S> =
-        read_genesis_json(&genesis_paths.sequencer_genesis_path)?;
-    let attester_incentives_config: AttesterIncentivesConfig<S> =
-        read_genesis_json(&genesis_paths.attester_incentives_genesis_path)?;
+/// Where the genesis configuration is read from.
+pub enum GenesisSource {
+    /// One JSON file per module.
+    Dir(GenesisPaths),
+    /// A single JSON or TOML document keyed by module name, see [`GenesisDocument`].
+    File(PathBuf),
+}
 
-    let prover_incentives_config: ProverIncentivesConfig<S> =
-        read_genesis_json(&genesis_paths.prover_incentives_genesis_path)?;
+impl GenesisSource {
+    /// Reads directories with [`GenesisPaths::from_dir`], and anything else as a single file.
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        if path.is_dir() {
+            Self::Dir(GenesisPaths::from_dir(path))
+        } else {
+            Self::File(path.to_path_buf())
+        }
+    }
+}
 
-    let nonces_config = ();
+impl From<GenesisPaths> for GenesisSource {
+    fn from(paths: GenesisPaths) -> Self {
+        Self::Dir(paths)
+    }
+}
+
+/// The genesis configuration of every module, keyed by module name.
+///
+/// Written as a single file, this is the whole genesis of the rollup, e.g. in TOML:
+///
+/// ```toml
+/// [bank.gas_token_config]
+/// token_name = "sov-token"
+/// # ...
+///
+/// [sequencer_registry]
+/// seq_bond = 10000000
+/// # ...
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GenesisDocument {
+    /// The configuration of every module.
+    pub modules: BTreeMap<String, Value>,
+}
+
+impl GenesisDocument {
+    /// Reads the genesis configuration of every module from `source`.
+    pub fn read(source: &GenesisSource) -> anyhow::Result<Self> {
+        match source {
+            GenesisSource::Dir(paths) => {
+                let modules = paths
+                    .modules()
+                    .into_iter()
+                    .map(|(module, path)| Ok((module.to_string(), read_genesis_json(path)?)))
+                    .collect::<anyhow::Result<_>>()?;
+                Ok(Self { modules })
+            }
+            GenesisSource::File(path) => Self::read_file(path),
+        }
+    }
 
-    let example_module_config = ExampleModuleConfig {};
+    /// Reads a single-file genesis. Files with a `.toml` extension are parsed as TOML,
+    /// anything else as JSON.
+    pub fn read_file(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read genesis file {}", path.display()))?;
 
-    let chain_state_config: ChainStateConfig<S> =
-        read_genesis_json(&genesis_paths.chain_state_genesis_path)?;
+        let document = if is_toml(path) {
+            toml::from_str(&contents).map_err(anyhow::Error::from)
+        } else {
+            serde_json::from_str(&contents).map_err(anyhow::Error::from)
+        };
+
+        document.with_context(|| format!("Invalid genesis file {}", path.display()))
+    }
+
+    /// Writes the document to `path`, as TOML if it has a `.toml` extension and as JSON
+    /// otherwise.
+    pub fn write_file(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = if is_toml(path) {
+            // TOML has no null: drop the fields set to null, which deserialize to `None`
+            // when they are missing.
+            let mut modules = serde_json::to_value(&self.modules)?;
+            remove_nulls(&mut modules);
+            toml::to_string_pretty(&modules)?
+        } else {
+            serde_json::to_string_pretty(self)?
+        };
+
+        std::fs::write(path, contents)
+            .with_context(|| format!("Failed to write genesis file {}", path.display()))
+    }
 
+    /// Writes the configuration of every module to `<module>.json` in `dir`, the layout read
+    /// by [`GenesisPaths::from_dir`].
+    pub fn write_dir(&self, dir: &Path) -> anyhow::Result<()> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create genesis directory {}", dir.display()))?;
+
+        for (module, config) in &self.modules {
+            let path = dir.join(format!("{module}.json"));
+            std::fs::write(&path, serde_json::to_string_pretty(config)? + "\n")
+                .with_context(|| format!("Failed to write genesis file {}", path.display()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Deserializes the configuration of `module`.
+    pub fn config<T: DeserializeOwned>(&self, module: &str) -> anyhow::Result<T> {
+        let Some(config) = self.modules.get(module) else {
+            bail!("The genesis of module `{module}` is missing");
+        };
+
+        serde_json::from_value(config.clone())
+            .with_context(|| format!("Invalid genesis for module `{module}`"))
+    }
+}
+
+/// Creates a new [`GenesisConfig`] from the genesis files. Fails with every problem found by
+/// [`validate_genesis`] if they are invalid.
+pub fn create_genesis_config<S: Spec>(
+    genesis_source: &GenesisSource,
+) -> anyhow::Result<<Runtime<S> as RuntimeTrait<S>>::GenesisConfig> {
+    validate_genesis::<S>(genesis_source)?;
+
+    let document = GenesisDocument::read(genesis_source)?;
+
+    let mut governance_config: GovernanceConfig<S> = document.config("governance")?;
+    governance_config.runtime_modules = RuntimeCall::<S>::MODULE_NAMES.map(String::from).to_vec();
+    governance_config.runtime_calls = RuntimeCall::<S>::runtime_calls();
+
+    let nonces_config = ();
+    let rollup_stats_config = ();
+    let session_keys_config = ();
     let blob_storage_config = ();
 
     Ok(GenesisConfig::new(
-        accounts_config,
+        document.config("accounts")?,
         nonces_config,
-        bank_config,
-        sequencer_registry_config,
-        attester_incentives_config,
-        prover_incentives_config,
-        example_module_config,
-        chain_state_config,
+        document.config("bank")?,
+        document.config("sequencer_registry")?,
+        document.config("attester_incentives")?,
+        document.config("prover_incentives")?,
+        document.config("example_module")?,
+        governance_config,
+        rollup_stats_config,
+        document.config("evm")?,
+        session_keys_config,
+        document.config("paymaster")?,
+        document.config("chain_state")?,
         blob_storage_config,
     ))
 }
 
-fn read_genesis_json<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
-    let contents = std::fs::read_to_string(path)?;
-    Ok(serde_json::from_str(&contents)?)
+fn read_genesis_json(path: &Path) -> anyhow::Result<Value> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read genesis file {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid genesis file {}", path.display()))
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "toml")
+}
+
+fn remove_nulls(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            fields.retain(|_, field| !field.is_null());
+            fields.values_mut().for_each(remove_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(remove_nulls),
+        _ => {}
+    }
 }
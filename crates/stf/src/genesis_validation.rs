@@ -0,0 +1,518 @@
+//! Validation of the genesis files, run before the genesis config is built.
+//!
+//! Every module's genesis is first deserialized into the config of the module, then the
+//! modules are checked against each other: balances cover the bonds, bonds are above the
+//! minimums, the gas token is consistent with `constants.toml` and no address is listed
+//! twice. Addresses are compared once parsed, so that differently cased spellings of the same
+//! address match. All problems are collected, so that a broken genesis can be fixed in a
+//! single pass.
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use example_module::ExampleModuleConfig;
+use governance::{GovernanceConfig, GOVERNANCE_MODULE};
+use paymaster::PaymasterConfig;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use sov_accounts::AccountConfig;
+use sov_attester_incentives::AttesterIncentivesConfig;
+use sov_bank::BankConfig;
+use sov_chain_state::ChainStateConfig;
+use sov_evm::EvmConfig;
+use sov_modules_api::macros::config_value;
+use sov_modules_api::Spec;
+use sov_prover_incentives::ProverIncentivesConfig;
+use sov_sequencer_registry::SequencerConfig;
+
+use crate::genesis_config::{GenesisDocument, GenesisSource, GENESIS_MODULES};
+use crate::RuntimeCall;
+
+/// A problem found in a genesis file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenesisIssue {
+    /// The file the problem was found in.
+    pub file: PathBuf,
+    /// What is wrong.
+    pub message: String,
+}
+
+impl fmt::Display for GenesisIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.file.display(), self.message)
+    }
+}
+
+/// Every problem found in a genesis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenesisValidationError {
+    /// The problems, in the order they were found.
+    pub issues: Vec<GenesisIssue>,
+}
+
+impl fmt::Display for GenesisValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid genesis, found {} problem(s):",
+            self.issues.len()
+        )?;
+        for issue in &self.issues {
+            write!(f, "\n  - {issue}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GenesisValidationError {}
+
+/// Checks the genesis read from `source`, returning every problem found.
+pub fn validate_genesis<S: Spec>(source: &GenesisSource) -> Result<(), GenesisValidationError> {
+    let (mut checker, document) = Checker::load(source);
+
+    let accounts = checker.read::<AccountConfig<S>>(&document, "accounts");
+    let bank = checker.read::<BankConfig<S>>(&document, "bank");
+    let sequencer = checker.read::<SequencerConfig<S>>(&document, "sequencer_registry");
+    let attesters = checker.read::<AttesterIncentivesConfig<S>>(&document, "attester_incentives");
+    let provers = checker.read::<ProverIncentivesConfig<S>>(&document, "prover_incentives");
+    let example_module = checker.read::<ExampleModuleConfig<S>>(&document, "example_module");
+    let governance = checker.read::<GovernanceConfig<S>>(&document, "governance");
+    checker.read::<EvmConfig>(&document, "evm");
+    let paymaster = checker.read::<PaymasterConfig<S>>(&document, "paymaster");
+    checker.read::<ChainStateConfig<S>>(&document, "chain_state");
+
+    if let Some(accounts) = accounts {
+        let entries = array(accounts, "/accounts");
+        checker.unique_by(
+            "accounts",
+            "account address",
+            entries.iter().map(|a| &a["address"]),
+            address_key::<S>,
+        );
+        checker.unique(
+            "accounts",
+            "credential id",
+            entries.iter().map(|a| &a["credential_id"]),
+        );
+    }
+
+    if let Some(example_module) = example_module {
+        checker.unique_by(
+            "example_module",
+            "writer",
+            array(example_module, "/writers").iter(),
+            address_key::<S>,
+        );
+    }
+
+    if let Some(governance) = governance {
+        for module in array(governance, "/paused_modules") {
+            if module.as_str() == Some(GOVERNANCE_MODULE) {
+                checker.report(
+                    "governance",
+                    format!("Cannot pause {module}, it is needed to unpause the others"),
+                );
+            } else if !module
+                .as_str()
+                .is_some_and(|module| RuntimeCall::<S>::MODULE_NAMES.contains(&module))
+            {
+                checker.report(
+                    "governance",
+                    format!("Cannot pause {module}, the runtime has no such module"),
+                );
+            }
+        }
+
+        for entry in array(governance, "/unregistered_calls") {
+            let module = entry["module"].as_str().unwrap_or_default();
+            if !RuntimeCall::<S>::MODULE_NAMES.contains(&module) {
+                checker.report(
+                    "governance",
+                    format!(
+                        "Cannot allow calls to {}, the runtime has no such module",
+                        entry["module"]
+                    ),
+                );
+            } else if let Some(call) = entry["call"].as_str() {
+                if !RuntimeCall::<S>::CALL_NAMES.contains(&(module, call)) {
+                    checker.report(
+                        "governance",
+                        format!(
+                            "Cannot allow call {} of {}, the module has no such call",
+                            entry["call"], entry["module"]
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(paymaster) = paymaster {
+        let sponsors = array(paymaster, "/sponsors");
+        checker.unique_by(
+            "paymaster",
+            "sponsor",
+            sponsors.iter().map(|s| &s["sponsor"]),
+            address_key::<S>,
+        );
+    }
+
+    // The remaining checks compare the bonds with the gas token balances.
+    let Some(bank) = bank else {
+        return checker.finish();
+    };
+    let balances = checker.gas_balances::<S>(bank);
+
+    // Bonds are taken from the gas token balance of their owner at genesis, so an address
+    // bonding in several modules must be able to pay for all of them.
+    let mut bonds = Bonds::default();
+
+    if let Some(sequencer) = sequencer {
+        let module = "sequencer_registry";
+        match (
+            sequencer["seq_rollup_address"].as_str(),
+            sequencer["seq_bond"].as_u64(),
+        ) {
+            (Some(address), Some(bond)) => {
+                checker.min_bond(
+                    module,
+                    "sequencer",
+                    address,
+                    bond,
+                    &sequencer["minimum_bond"],
+                );
+                bonds.add(module, normalize_address::<S>(address), bond);
+            }
+            _ => checker.report(module, "`seq_rollup_address` and `seq_bond` must be set"),
+        }
+    }
+
+    if let Some(attesters) = attesters {
+        let module = "attester_incentives";
+        let initial = array(attesters, "/initial_attesters");
+        checker.unique_by(
+            module,
+            "attester",
+            initial.iter().map(|a| &a[0]),
+            address_key::<S>,
+        );
+        for (address, bond) in checker.pairs(module, "initial_attesters", initial) {
+            checker.min_bond(
+                module,
+                "attester",
+                address,
+                bond,
+                &attesters["minimum_attester_bond"],
+            );
+            bonds.add(module, normalize_address::<S>(address), bond);
+        }
+    }
+
+    if let Some(provers) = provers {
+        let module = "prover_incentives";
+        let initial = array(provers, "/initial_provers");
+        checker.unique_by(
+            module,
+            "prover",
+            initial.iter().map(|p| &p[0]),
+            address_key::<S>,
+        );
+        for (address, bond) in checker.pairs(module, "initial_provers", initial) {
+            checker.min_bond(module, "prover", address, bond, &provers["minimum_bond"]);
+            bonds.add(module, normalize_address::<S>(address), bond);
+        }
+    }
+
+    for (address, (required, modules)) in bonds.0 {
+        let balance = balances.get(&address).copied().unwrap_or_default();
+        if balance < required {
+            for module in modules {
+                checker.report(
+                    module,
+                    format!(
+                        "{address} bonds {required} gas tokens in total but only holds \
+                         {balance} in the `bank` genesis"
+                    ),
+                );
+            }
+        }
+    }
+
+    checker.finish()
+}
+
+/// Bonds required from every address, keyed by [`normalize_address`], with the modules they
+/// come from.
+#[derive(Default)]
+struct Bonds(BTreeMap<String, (u64, Vec<&'static str>)>);
+
+impl Bonds {
+    fn add(&mut self, module: &'static str, address: String, bond: u64) {
+        let (required, modules) = self.0.entry(address).or_default();
+        *required = required.saturating_add(bond);
+        if !modules.contains(&module) {
+            modules.push(module);
+        }
+    }
+}
+
+struct Checker {
+    issues: Vec<GenesisIssue>,
+    /// The file the genesis of every module is read from.
+    files: BTreeMap<&'static str, PathBuf>,
+    /// Whether every module is read from the same file, in which case issues name the module.
+    single_file: bool,
+}
+
+impl Checker {
+    /// Reads the genesis of every module from `source`, reporting the files that cannot be
+    /// read. Those modules are missing from the returned document.
+    fn load(source: &GenesisSource) -> (Self, GenesisDocument) {
+        let mut document = GenesisDocument::default();
+
+        let mut checker = match source {
+            GenesisSource::Dir(paths) => Self {
+                issues: Vec::new(),
+                files: paths
+                    .modules()
+                    .into_iter()
+                    .map(|(module, path)| (module, path.to_path_buf()))
+                    .collect(),
+                single_file: false,
+            },
+            GenesisSource::File(path) => Self {
+                issues: Vec::new(),
+                files: GENESIS_MODULES
+                    .into_iter()
+                    .map(|module| (module, path.clone()))
+                    .collect(),
+                single_file: true,
+            },
+        };
+
+        match source {
+            GenesisSource::Dir(paths) => {
+                for (module, path) in paths.modules() {
+                    let contents = std::fs::read_to_string(path)
+                        .map_err(|e| format!("Failed to read the file: {e}"))
+                        .and_then(|contents| {
+                            serde_json::from_str::<Value>(&contents)
+                                .map_err(|e| format!("Invalid JSON: {e}"))
+                        });
+                    match contents {
+                        Ok(config) => {
+                            document.modules.insert(module.to_string(), config);
+                        }
+                        Err(message) => checker.report_file(path, message),
+                    }
+                }
+            }
+            GenesisSource::File(path) => match GenesisDocument::read_file(path) {
+                Ok(read) => {
+                    for module in read.modules.keys() {
+                        if !GENESIS_MODULES.contains(&module.as_str()) {
+                            checker.report_file(path, format!("Unknown module `{module}`"));
+                        }
+                    }
+                    for module in GENESIS_MODULES {
+                        if !read.modules.contains_key(module) {
+                            checker.report(module, "The genesis of the module is missing");
+                        }
+                    }
+                    document = read;
+                }
+                Err(e) => checker.report_file(path, format!("{:#}", e.root_cause())),
+            },
+        }
+
+        (checker, document)
+    }
+
+    fn report_file(&mut self, file: &Path, message: impl Into<String>) {
+        self.issues.push(GenesisIssue {
+            file: file.to_path_buf(),
+            message: message.into(),
+        });
+    }
+
+    fn report(&mut self, module: &str, message: impl Into<String>) {
+        let file = self.files[module].clone();
+        let message = message.into();
+        if self.single_file {
+            self.report_file(&file, format!("[{module}] {message}"));
+        } else {
+            self.report_file(&file, message);
+        }
+    }
+
+    fn finish(self) -> Result<(), GenesisValidationError> {
+        if self.issues.is_empty() {
+            Ok(())
+        } else {
+            Err(GenesisValidationError {
+                issues: self.issues,
+            })
+        }
+    }
+
+    /// Checks that the genesis of `module` deserializes into `T`. Returns it as JSON for the
+    /// cross-module checks, `None` if it is missing or invalid.
+    fn read<'a, T: DeserializeOwned>(
+        &mut self,
+        document: &'a GenesisDocument,
+        module: &str,
+    ) -> Option<&'a Value> {
+        let config = document.modules.get(module)?;
+
+        match serde_json::from_value::<T>(config.clone()) {
+            Ok(_) => Some(config),
+            Err(e) => {
+                self.report(module, format!("Invalid config: {e}"));
+                None
+            }
+        }
+    }
+
+    /// Reports every value of `values` that appears more than once. Hex and bech32 strings
+    /// are compared case-insensitively.
+    fn unique<'a>(&mut self, module: &str, what: &str, values: impl Iterator<Item = &'a Value>) {
+        self.unique_by(module, what, values, |value| {
+            value
+                .as_str()
+                .map(str::to_lowercase)
+                .unwrap_or_else(|| value.to_string())
+        });
+    }
+
+    /// Reports every value of `values` whose `key` appears more than once.
+    fn unique_by<'a>(
+        &mut self,
+        module: &str,
+        what: &str,
+        values: impl Iterator<Item = &'a Value>,
+        key: impl Fn(&Value) -> String,
+    ) {
+        let mut seen = HashSet::new();
+        for value in values {
+            if !seen.insert(key(value)) {
+                self.report(module, format!("Duplicate {what} {value}"));
+            }
+        }
+    }
+
+    /// Reads `[address, amount]` pairs, reporting the malformed ones.
+    fn pairs<'a>(
+        &mut self,
+        module: &str,
+        field: &str,
+        entries: &'a [Value],
+    ) -> Vec<(&'a str, u64)> {
+        entries
+            .iter()
+            .filter_map(|entry| match (entry[0].as_str(), entry[1].as_u64()) {
+                (Some(address), Some(amount)) => Some((address, amount)),
+                _ => {
+                    self.report(module, format!("Malformed entry {entry} in `{field}`"));
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the gas token balance of every address, keyed by [`normalize_address`], and
+    /// checks the gas token itself.
+    fn gas_balances<S: Spec>(&mut self, bank: &Value) -> BTreeMap<String, u64> {
+        let gas_token = &bank["gas_token_config"];
+        let entries = array(gas_token, "/address_and_balances");
+        self.unique_by(
+            "bank",
+            "gas token holder",
+            entries.iter().map(|e| &e[0]),
+            address_key::<S>,
+        );
+
+        // The gas token is created under `GAS_TOKEN_ID`, no other token can use that id.
+        let gas_token_id = sov_bank::config_gas_token_id().to_string();
+        for token in array(bank, "/tokens") {
+            if token["token_id"].as_str() == Some(gas_token_id.as_str()) {
+                self.report(
+                    "bank",
+                    format!(
+                        "Token {} uses the id of the gas token, `GAS_TOKEN_ID` in constants.toml",
+                        token["token_name"]
+                    ),
+                );
+            }
+            if token["token_name"] == gas_token["token_name"] {
+                self.report(
+                    "bank",
+                    format!(
+                        "Token {} has the name of the gas token",
+                        token["token_name"]
+                    ),
+                );
+            }
+        }
+        self.unique(
+            "bank",
+            "token id",
+            array(bank, "/tokens").iter().map(|t| &t["token_id"]),
+        );
+
+        self.pairs("bank", "gas_token_config.address_and_balances", entries)
+            .into_iter()
+            .map(|(address, balance)| (normalize_address::<S>(address), balance))
+            .collect()
+    }
+
+    /// Reports `bond` if it is worth less than `minimum`, a multidimensional gas amount, at the
+    /// initial base fee.
+    fn min_bond(&mut self, module: &str, role: &str, address: &str, bond: u64, minimum: &Value) {
+        let base_fee: &[u64] = &config_value!("INITIAL_BASE_FEE_PER_GAS");
+        let minimum_value = minimum.as_array().and_then(|dimensions| {
+            dimensions
+                .iter()
+                .zip(base_fee)
+                .try_fold(0u64, |total, (gas, price)| {
+                    total.checked_add(gas.as_u64()?.checked_mul(*price)?)
+                })
+        });
+
+        match minimum_value {
+            Some(minimum_value) if bond < minimum_value => self.report(
+                module,
+                format!(
+                    "The bond of {role} {address} is {bond}, below the minimum bond {minimum} \
+                     worth {minimum_value} at the initial base fee"
+                ),
+            ),
+            Some(_) => {}
+            None => self.report(module, format!("Invalid minimum bond {minimum}")),
+        }
+    }
+}
+
+/// The canonical spelling of `address`, as printed once parsed into `S::Address`. Addresses
+/// that do not parse are kept as they are: the module configs already reported them.
+fn normalize_address<S: Spec>(address: &str) -> String {
+    serde_json::from_value::<S::Address>(Value::String(address.to_string()))
+        .map(|address| address.to_string())
+        .unwrap_or_else(|_| address.to_string())
+}
+
+/// The key of an address value in the uniqueness checks.
+fn address_key<S: Spec>(value: &Value) -> String {
+    match value.as_str() {
+        Some(address) => normalize_address::<S>(address),
+        None => value.to_string(),
+    }
+}
+
+/// The array at `pointer`, empty if there is none.
+fn array<'a>(value: &'a Value, pointer: &str) -> &'a [Value] {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
@@ -4,16 +4,59 @@
 //! - At the beginning and end of each batch ("blob")
 //! - At the beginning and end of each slot (DA layer block)
 use super::runtime::Runtime;
+#[cfg(feature = "native")]
+use rollup_stats::BatchOutcome;
 use sov_modules_api::hooks::KernelSlotHooks;
 use sov_modules_api::hooks::{ApplyBatchHooks, FinalizeHook, SlotHooks, TxHooks};
+use sov_modules_api::prelude::UnwrapInfallible;
+#[cfg(feature = "native")]
+use sov_modules_api::SequencerOutcome;
 use sov_modules_api::TxScratchpad;
 use sov_modules_api::{
-    BatchSequencerReceipt, Spec, StateCheckpoint, StateReaderAndWriter, WorkingSet,
+    BatchSequencerReceipt, Spec, StateCheckpoint, StateReader, StateReaderAndWriter, WorkingSet,
 };
 use sov_rollup_interface::da::DaSpec;
 use sov_state::namespaces::Accessory;
+use sov_state::namespaces::Kernel;
 use sov_state::Storage;
 
+impl<S: Spec> Runtime<S> {
+    /// The rollup height of the slot being executed, as counted by the `chain_state` module.
+    pub(crate) fn rollup_height(&self, state: &mut impl StateReader<Kernel>) -> u64 {
+        self.chain_state
+            .true_slot_number(state)
+            .unwrap_infallible()
+            .get()
+    }
+}
+
+/// The rollup stats are only collected by native nodes, see [`rollup_stats::RollupStats`].
+#[cfg(feature = "native")]
+impl<S: Spec> Runtime<S> {
+    /// Records the outcome of a batch in the rollup stats.
+    fn record_batch_stats(
+        &self,
+        result: &BatchSequencerReceipt<S::Da>,
+        state: &mut TxScratchpad<S::Storage>,
+    ) {
+        let outcome = match &result.inner {
+            SequencerOutcome::Rewarded(amount) => BatchOutcome::Rewarded(*amount),
+            SequencerOutcome::Penalized(amount) => BatchOutcome::Penalized(*amount),
+            SequencerOutcome::Slashed { .. } => BatchOutcome::Slashed,
+            SequencerOutcome::Ignored => BatchOutcome::Ignored,
+        };
+
+        self.rollup_stats.record_batch(
+            outcome,
+            result
+                .tx_receipts
+                .iter()
+                .map(|receipt| receipt.gas_used.as_slice()),
+            state,
+        );
+    }
+}
+
 impl<S: Spec> TxHooks for Runtime<S> {
     type Spec = S;
     type TxState = WorkingSet<S>;
@@ -25,25 +68,51 @@ impl<S: Spec> ApplyBatchHooks for Runtime<S> {
 
     fn begin_batch_hook(
         &self,
-        _sender: &<S::Da as DaSpec>::Address,
-        _state: &mut TxScratchpad<S::Storage>,
+        sender: &<S::Da as DaSpec>::Address,
+        state: &mut TxScratchpad<S::Storage>,
     ) -> anyhow::Result<()> {
+        #[cfg(feature = "native")]
+        self.rollup_stats.begin_batch(sender);
+
+        let slot = self.rollup_height(state);
+        if let Err(e) = self.governance.check_sequencer(sender, slot, state) {
+            #[cfg(feature = "native")]
+            self.rollup_stats.reject_batch();
+            return Err(e);
+        }
+
         Ok(())
     }
 
-    fn end_batch_hook(&self, _result: &Self::BatchResult, _state: &mut TxScratchpad<S::Storage>) {}
+    #[cfg_attr(not(feature = "native"), allow(unused_variables))]
+    fn end_batch_hook(&self, result: &Self::BatchResult, state: &mut TxScratchpad<S::Storage>) {
+        #[cfg(feature = "native")]
+        self.record_batch_stats(result, state);
+    }
 }
 
 impl<S: Spec> SlotHooks for Runtime<S> {
     type Spec = S;
+
     fn begin_slot_hook(
         &self,
-        _pre_state_root: &<<S as Spec>::Storage as Storage>::Root,
-        _versioned_working_set: &mut StateCheckpoint<S::Storage>,
+        pre_state_root: &<<S as Spec>::Storage as Storage>::Root,
+        versioned_working_set: &mut StateCheckpoint<S::Storage>,
     ) {
+        self.evm
+            .begin_slot_hook(pre_state_root, versioned_working_set);
+
+        #[cfg(feature = "native")]
+        {
+            let slot_number = self.rollup_height(versioned_working_set);
+            self.rollup_stats
+                .begin_slot(slot_number, versioned_working_set);
+        }
     }
 
-    fn end_slot_hook(&self, _state: &mut StateCheckpoint<S::Storage>) {}
+    fn end_slot_hook(&self, state: &mut StateCheckpoint<S::Storage>) {
+        self.evm.end_slot_hook(state);
+    }
 }
 
 impl<S: Spec> FinalizeHook for Runtime<S> {
@@ -51,9 +120,14 @@ impl<S: Spec> FinalizeHook for Runtime<S> {
 
     fn finalize_hook(
         &self,
-        _root_hash: &<<S as Spec>::Storage as Storage>::Root,
-        _accessory_working_set: &mut impl StateReaderAndWriter<Accessory>,
+        root_hash: &<<S as Spec>::Storage as Storage>::Root,
+        accessory_working_set: &mut impl StateReaderAndWriter<Accessory>,
     ) {
+        self.evm.finalize_hook(root_hash, accessory_working_set);
+
+        #[cfg(feature = "native")]
+        self.rollup_stats
+            .finalize_slot(root_hash.as_ref().to_vec(), accessory_working_set);
     }
 }
 
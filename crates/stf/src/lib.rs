@@ -1,10 +1,16 @@
 //! The rollup State Transition Function.
 
 pub mod authentication;
+pub mod capabilities;
 #[cfg(feature = "native")]
 pub mod genesis_config;
+#[cfg(feature = "native")]
+pub mod genesis_validation;
 pub mod hooks;
+pub mod multisig;
 pub mod runtime;
+#[cfg(feature = "native")]
+pub mod state_export;
 
 pub use runtime::*;
 use sov_modules_stf_blueprint::StfBlueprint;
@@ -0,0 +1,130 @@
+//! M-of-N multisig authentication.
+//!
+//! A multisig transaction is a regular `sov-modules` transaction signed by one of the N keys
+//! (the submitter), together with the signatures of the other signers over the hash of that
+//! transaction. Once enough signatures are verified, the transaction is authorized with the
+//! credential of the multisig instead of the submitter's. The multisig credential maps to an
+//! account like any other credential: register it in the `accounts` genesis or with
+//! `sov_accounts::CallMessage::InsertCredentialId`.
+//!
+//! The runtime charges the fixed cost of a signature verification for every co-signature
+//! before verifying them.
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::digest::Digest;
+use sov_modules_api::{CredentialId, CryptoSpec, PublicKey, Signature, Spec};
+
+/// Maximum number of keys in a multisig.
+pub const MAX_MULTISIG_KEYS: usize = 16;
+
+type Hasher<S> = <<S as Spec>::CryptoSpec as CryptoSpec>::Hasher;
+
+/// An M-of-N set of keys.
+#[derive(Debug, PartialEq, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(bound = "S::PublicKey: Serialize + serde::de::DeserializeOwned")]
+pub struct Multisig<S: Spec> {
+    /// Number of distinct signatures required, M.
+    pub threshold: u8,
+    /// The N keys allowed to sign.
+    pub public_keys: Vec<S::PublicKey>,
+}
+
+/// The signature of one of the keys of a [`Multisig`].
+#[derive(Debug, PartialEq, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(bound = "S::Signature: Serialize + serde::de::DeserializeOwned")]
+pub struct CoSignature<S: Spec> {
+    /// Index of the signer in [`Multisig::public_keys`].
+    pub signer: u8,
+    /// Signature over [`signing_message`].
+    pub signature: S::Signature,
+}
+
+/// Everything but the submitter's transaction in an `Auth::Multisig` envelope.
+#[derive(Debug, PartialEq, Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(bound = "S::PublicKey: Serialize + serde::de::DeserializeOwned, \
+    S::Signature: Serialize + serde::de::DeserializeOwned")]
+pub struct MultisigProof<S: Spec> {
+    /// The multisig the transaction is sent from.
+    pub multisig: Multisig<S>,
+    /// Signatures of the signers other than the submitter.
+    pub co_signatures: Vec<CoSignature<S>>,
+}
+
+impl<S: Spec> Multisig<S> {
+    /// The credential of the multisig: the hash of its borsh encoding. Changing the threshold
+    /// or the keys therefore yields a different credential.
+    pub fn credential_id(&self) -> CredentialId {
+        let bytes = borsh::to_vec(self).expect("Serialization to a vector never fails");
+        CredentialId(Hasher::<S>::digest(bytes).into())
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        let n = self.public_keys.len();
+        anyhow::ensure!(
+            (1..=MAX_MULTISIG_KEYS).contains(&n),
+            "A multisig must have between 1 and {MAX_MULTISIG_KEYS} keys, got {n}"
+        );
+        anyhow::ensure!(
+            (1..=n).contains(&usize::from(self.threshold)),
+            "The threshold of a multisig must be between 1 and {n}, got {}",
+            self.threshold
+        );
+        for (i, key) in self.public_keys.iter().enumerate() {
+            anyhow::ensure!(
+                !self.public_keys[..i].contains(key),
+                "Key {i} appears more than once in the multisig"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The message co-signers sign: the hash of the submitter's serialized transaction, which
+/// covers the call, the nonce, the fees and the chain id.
+pub fn signing_message<S: Spec>(tx: &[u8]) -> Vec<u8> {
+    Hasher::<S>::digest(tx).to_vec()
+}
+
+impl<S: Spec> MultisigProof<S> {
+    /// Checks that `tx`, submitted with the credential `submitter`, carries at least
+    /// `threshold` distinct valid signatures, and returns the credential of the multisig.
+    pub fn verify(&self, tx: &[u8], submitter: &CredentialId) -> anyhow::Result<CredentialId> {
+        let multisig = &self.multisig;
+        multisig.validate()?;
+
+        let mut signed = vec![false; multisig.public_keys.len()];
+        let submitter_index = multisig
+            .public_keys
+            .iter()
+            .position(|key| &key.credential_id::<Hasher<S>>() == submitter)
+            .ok_or_else(|| {
+                anyhow::anyhow!("The submitter of the transaction is not a signer of the multisig")
+            })?;
+        signed[submitter_index] = true;
+
+        let message = signing_message::<S>(tx);
+        for co_signature in &self.co_signatures {
+            let index = usize::from(co_signature.signer);
+            let key = multisig
+                .public_keys
+                .get(index)
+                .ok_or_else(|| anyhow::anyhow!("Unknown signer {index}"))?;
+            anyhow::ensure!(!signed[index], "Signer {index} signed more than once");
+
+            co_signature
+                .signature
+                .verify(key, &message)
+                .map_err(|e| anyhow::anyhow!("Invalid signature of signer {index}: {e}"))?;
+            signed[index] = true;
+        }
+
+        let signers = signed.iter().filter(|signed| **signed).count();
+        anyhow::ensure!(
+            signers >= usize::from(multisig.threshold),
+            "The transaction has {signers} signatures, the multisig requires {}",
+            multisig.threshold
+        );
+
+        Ok(multisig.credential_id())
+    }
+}
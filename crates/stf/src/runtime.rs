@@ -6,7 +6,10 @@
 //!   3. Update `genesis.json` with any additional data required by your new module
 
 use sov_capabilities::StandardProvenRollupCapabilities as StandardCapabilities;
+#[cfg(not(feature = "soft-confirmations"))]
 use sov_kernels::basic::BasicKernel;
+#[cfg(feature = "soft-confirmations")]
+use sov_kernels::soft_confirmations::SoftConfirmationsKernel;
 use sov_modules_api::capabilities::{AuthorizationData, Guard, HasCapabilities, HasKernel};
 #[cfg(feature = "native")]
 use sov_modules_api::macros::{expose_rpc, CliWallet};
@@ -15,8 +18,9 @@ use sov_modules_api::BlobDataWithId;
 use sov_modules_api::{DispatchCall, Event, Genesis, MessageCodec, Spec};
 use sov_rollup_interface::da::DaSpec;
 
+use crate::capabilities::RollupCapabilities;
 #[cfg(feature = "native")]
-use crate::genesis_config::GenesisPaths;
+use crate::genesis_config::GenesisSource;
 
 /// The runtime defines the logic of the rollup.
 ///
@@ -66,6 +70,16 @@ pub struct Runtime<S: Spec> {
     pub prover_incentives: sov_prover_incentives::ProverIncentives<S>,
     /// The example module.
     pub example_module: example_module::ExampleModule<S>,
+    /// The governance module, holding the runtime policies such as paused modules.
+    pub governance: governance::Governance<S>,
+    /// The rollup stats module, collecting per-slot statistics from the runtime hooks.
+    pub rollup_stats: rollup_stats::RollupStats<S>,
+    /// The EVM module, executing Ethereum transactions authenticated with `Auth::Evm`.
+    pub evm: sov_evm::Evm<S>,
+    /// The session keys module, holding the scoped permissions granted to session keys.
+    pub session_keys: session_keys::SessionKeys<S>,
+    /// The paymaster module, letting sponsors pay the gas of other users' transactions.
+    pub paymaster: paymaster::Paymaster<S>,
     /// The Chain state module.
     pub chain_state: sov_chain_state::ChainState<S>,
     /// The Blob storage module.
@@ -79,7 +93,7 @@ where
     type GenesisConfig = GenesisConfig<S>;
 
     #[cfg(feature = "native")]
-    type GenesisPaths = GenesisPaths;
+    type GenesisPaths = GenesisSource;
 
     #[cfg(feature = "native")]
     fn endpoints(
@@ -108,29 +122,233 @@ where
     }
 }
 
+impl<S: Spec> RuntimeCall<S> {
+    /// The names of all the modules of the runtime, as returned by [`Self::module_name`].
+    pub const MODULE_NAMES: [&'static str; 14] = [
+        "accounts",
+        "nonces",
+        "bank",
+        "sequencer_registry",
+        "attester_incentives",
+        "prover_incentives",
+        "example_module",
+        "governance",
+        "rollup_stats",
+        "evm",
+        "session_keys",
+        "paymaster",
+        "chain_state",
+        "blob_storage",
+    ];
+
+    /// The `(module, call)` names of all the named calls of the runtime, as returned by
+    /// [`Self::module_name`] and [`Self::call_name`].
+    pub const CALL_NAMES: &'static [(&'static str, &'static str)] = &[
+        ("accounts", "insert_credential_id"),
+        ("bank", "create_token"),
+        ("bank", "transfer"),
+        ("bank", "burn"),
+        ("bank", "mint"),
+        ("bank", "freeze"),
+        ("sequencer_registry", "register"),
+        ("sequencer_registry", "deposit"),
+        ("attester_incentives", "register_attester"),
+        ("attester_incentives", "begin_exit_attester"),
+        ("attester_incentives", "exit_attester"),
+        ("attester_incentives", "register_challenger"),
+        ("attester_incentives", "exit_challenger"),
+        ("prover_incentives", "register"),
+        ("prover_incentives", "deposit"),
+        ("prover_incentives", "exit"),
+        ("example_module", "set_value"),
+        ("example_module", "transfer_ownership"),
+        ("example_module", "add_writer"),
+        ("example_module", "remove_writer"),
+        ("example_module", "set_fee"),
+        ("example_module", "add"),
+        ("example_module", "sub"),
+        ("example_module", "mul"),
+        ("example_module", "div"),
+        ("example_module", "mod"),
+        ("example_module", "pow"),
+        ("example_module", "batch"),
+        ("example_module", "evaluate"),
+        ("governance", "pause_module"),
+        ("governance", "unpause_module"),
+        ("governance", "transfer_governor"),
+        ("governance", "set_sequencer_policy"),
+        ("governance", "set_unregistered_calls"),
+        ("session_keys", "grant"),
+        ("session_keys", "revoke"),
+        ("paymaster", "set_policy"),
+        ("paymaster", "remove_policy"),
+    ];
+
+    /// The `(module, call)` pairs of [`Self::CALL_NAMES`], as the `governance` module stores
+    /// them.
+    pub fn runtime_calls() -> Vec<(String, String)> {
+        Self::CALL_NAMES
+            .iter()
+            .map(|(module, call)| (module.to_string(), call.to_string()))
+            .collect()
+    }
+
+    /// The name of the runtime field holding the module this call is dispatched to.
+    /// These are the names used by the `governance` module to pause modules.
+    pub fn module_name(&self) -> &'static str {
+        match self {
+            RuntimeCall::Accounts(_) => "accounts",
+            RuntimeCall::Nonces(_) => "nonces",
+            RuntimeCall::Bank(_) => "bank",
+            RuntimeCall::SequencerRegistry(_) => "sequencer_registry",
+            RuntimeCall::AttesterIncentives(_) => "attester_incentives",
+            RuntimeCall::ProverIncentives(_) => "prover_incentives",
+            RuntimeCall::ExampleModule(_) => "example_module",
+            RuntimeCall::Governance(_) => "governance",
+            RuntimeCall::RollupStats(_) => "rollup_stats",
+            RuntimeCall::Evm(_) => "evm",
+            RuntimeCall::SessionKeys(_) => "session_keys",
+            RuntimeCall::Paymaster(_) => "paymaster",
+            RuntimeCall::ChainState(_) => "chain_state",
+            RuntimeCall::BlobStorage(_) => "blob_storage",
+        }
+    }
+
+    /// The name of the call message variant, as it appears in the JSON representation of
+    /// the call (e.g. `register` for a sequencer registration). These are the names used by
+    /// the `governance` module to allow calls from unregistered sequencers. `None` if the
+    /// module has no named calls.
+    ///
+    /// The calls of the SDK modules are matched with a fallback, so that a variant added
+    /// upstream is left unnamed, and only allowed with every call of its module, instead of
+    /// failing to compile.
+    #[allow(unreachable_patterns)]
+    pub fn call_name(&self) -> Option<&'static str> {
+        match self {
+            RuntimeCall::Accounts(call) => match call {
+                sov_accounts::CallMessage::InsertCredentialId { .. } => {
+                    Some("insert_credential_id")
+                }
+                _ => None,
+            },
+            RuntimeCall::Bank(call) => match call {
+                sov_bank::CallMessage::CreateToken { .. } => Some("create_token"),
+                sov_bank::CallMessage::Transfer { .. } => Some("transfer"),
+                sov_bank::CallMessage::Burn { .. } => Some("burn"),
+                sov_bank::CallMessage::Mint { .. } => Some("mint"),
+                sov_bank::CallMessage::Freeze { .. } => Some("freeze"),
+                _ => None,
+            },
+            RuntimeCall::SequencerRegistry(call) => match call {
+                sov_sequencer_registry::CallMessage::Register { .. } => Some("register"),
+                sov_sequencer_registry::CallMessage::Deposit { .. } => Some("deposit"),
+                _ => None,
+            },
+            RuntimeCall::AttesterIncentives(call) => match call {
+                sov_attester_incentives::CallMessage::RegisterAttester { .. } => {
+                    Some("register_attester")
+                }
+                sov_attester_incentives::CallMessage::BeginExitAttester { .. } => {
+                    Some("begin_exit_attester")
+                }
+                sov_attester_incentives::CallMessage::ExitAttester { .. } => Some("exit_attester"),
+                sov_attester_incentives::CallMessage::RegisterChallenger { .. } => {
+                    Some("register_challenger")
+                }
+                sov_attester_incentives::CallMessage::ExitChallenger { .. } => {
+                    Some("exit_challenger")
+                }
+                _ => None,
+            },
+            RuntimeCall::ProverIncentives(call) => match call {
+                sov_prover_incentives::CallMessage::Register { .. } => Some("register"),
+                sov_prover_incentives::CallMessage::Deposit { .. } => Some("deposit"),
+                sov_prover_incentives::CallMessage::Exit { .. } => Some("exit"),
+                _ => None,
+            },
+            RuntimeCall::ExampleModule(call) => Some(match call {
+                example_module::CallMessage::SetValue { .. } => "set_value",
+                example_module::CallMessage::TransferOwnership { .. } => "transfer_ownership",
+                example_module::CallMessage::AddWriter { .. } => "add_writer",
+                example_module::CallMessage::RemoveWriter { .. } => "remove_writer",
+                example_module::CallMessage::SetFee { .. } => "set_fee",
+                example_module::CallMessage::Add { .. } => "add",
+                example_module::CallMessage::Sub { .. } => "sub",
+                example_module::CallMessage::Mul { .. } => "mul",
+                example_module::CallMessage::Div { .. } => "div",
+                example_module::CallMessage::Mod { .. } => "mod",
+                example_module::CallMessage::Pow { .. } => "pow",
+                example_module::CallMessage::Batch { .. } => "batch",
+                example_module::CallMessage::Evaluate { .. } => "evaluate",
+            }),
+            RuntimeCall::Governance(call) => Some(match call {
+                governance::CallMessage::PauseModule { .. } => "pause_module",
+                governance::CallMessage::UnpauseModule { .. } => "unpause_module",
+                governance::CallMessage::TransferGovernor { .. } => "transfer_governor",
+                governance::CallMessage::SetSequencerPolicy { .. } => "set_sequencer_policy",
+                governance::CallMessage::SetUnregisteredCalls { .. } => "set_unregistered_calls",
+            }),
+            RuntimeCall::SessionKeys(call) => Some(match call {
+                session_keys::CallMessage::Grant { .. } => "grant",
+                session_keys::CallMessage::Revoke { .. } => "revoke",
+            }),
+            RuntimeCall::Paymaster(call) => Some(match call {
+                paymaster::CallMessage::SetPolicy { .. } => "set_policy",
+                paymaster::CallMessage::RemovePolicy => "remove_policy",
+            }),
+            // A single-field struct wrapping the RLP-encoded transaction.
+            RuntimeCall::Evm(_) => None,
+            // These modules cannot be called.
+            RuntimeCall::Nonces(_)
+            | RuntimeCall::RollupStats(_)
+            | RuntimeCall::ChainState(_)
+            | RuntimeCall::BlobStorage(_) => None,
+        }
+    }
+}
+
 impl<S: Spec> HasCapabilities<S> for Runtime<S> {
-    type Capabilities<'a> = StandardCapabilities<'a, S>;
+    type Capabilities<'a> = RollupCapabilities<'a, S>;
     type AuthorizationData = AuthorizationData<S>;
 
     fn capabilities(&self) -> Guard<Self::Capabilities<'_>> {
-        Guard::new(StandardCapabilities {
-            bank: &self.bank,
-            sequencer_registry: &self.sequencer_registry,
-            accounts: &self.accounts,
-            nonces: &self.nonces,
-            attester_incentives: &self.attester_incentives,
-            prover_incentives: &self.prover_incentives,
+        Guard::new(RollupCapabilities {
+            standard: StandardCapabilities {
+                bank: &self.bank,
+                sequencer_registry: &self.sequencer_registry,
+                accounts: &self.accounts,
+                nonces: &self.nonces,
+                attester_incentives: &self.attester_incentives,
+                prover_incentives: &self.prover_incentives,
+            },
+            paymaster: &self.paymaster,
+            session_keys: &self.session_keys,
         })
     }
 }
 
+/// `true` if the runtime was built with the `soft-confirmations` feature, in which case
+/// batches from other senders than the preferred sequencer are deferred by up to
+/// `DEFERRED_SLOTS_COUNT` slots (see `constants.toml`).
+pub const SOFT_CONFIRMATIONS: bool = cfg!(feature = "soft-confirmations");
+
 impl<S: Spec> HasKernel<S> for Runtime<S> {
     type BlobType = BlobDataWithId;
-    //type Kernel<'a> = SoftConfirmationsKernel<'a, S>;
+    #[cfg(feature = "soft-confirmations")]
+    type Kernel<'a> = SoftConfirmationsKernel<'a, S>;
+    #[cfg(not(feature = "soft-confirmations"))]
     type Kernel<'a> = BasicKernel<'a, S>;
 
+    #[cfg(feature = "soft-confirmations")]
+    fn inner(&self) -> Guard<Self::Kernel<'_>> {
+        Guard::new(SoftConfirmationsKernel {
+            chain_state: &self.chain_state,
+            blob_storage: &self.blob_storage,
+        })
+    }
+
+    #[cfg(not(feature = "soft-confirmations"))]
     fn inner(&self) -> Guard<Self::Kernel<'_>> {
-        //Guard::new(SoftConfirmationsKernel {
         Guard::new(BasicKernel {
             chain_state: &self.chain_state,
             blob_storage: &self.blob_storage,
@@ -0,0 +1,266 @@
+//! Export of the live state into a genesis, for coordinated restarts.
+//!
+//! State maps cannot be enumerated, so the state is read for a known set of addresses: every
+//! address found in the genesis the chain was started from, plus the addresses passed
+//! explicitly. Accounts that first appeared after genesis must be passed to keep their
+//! balances. [`check_total_supply`] detects the balances that were left out.
+//!
+//! The exported genesis carries over:
+//! - the balances of the gas token and of the tokens created at genesis,
+//! - the bond of the preferred sequencer,
+//! - the attesters and provers still bonded, with their current bonds,
+//! - the state of the example module: value, owner, writers, fee and registers.
+//!
+//! Everything else is copied from the original genesis. In particular, `accounts` only maps
+//! the credentials of the original genesis: credentials registered since then cannot be
+//! looked up by address, and are not exported. Bonds are locked again when the new chain starts, so they are added back to the
+//! balances of their owners. Challengers have no genesis, so their bonds are only returned to
+//! them. This way the total supply of the gas token is preserved.
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context};
+use serde_json::{json, Value};
+use sov_attester_incentives::Role;
+use sov_bank::TokenId;
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_modules_api::{ApiStateAccessor, DaSpec, Spec};
+
+use crate::genesis_config::GenesisDocument;
+use crate::Runtime;
+
+/// Builds a genesis from `state`, starting from the genesis the chain was started from.
+///
+/// Balances held by addresses that are not known are dropped: check the result with
+/// [`check_total_supply`].
+pub fn export_state<S: Spec>(
+    base: &GenesisDocument,
+    extra_addresses: &[S::Address],
+    state: &mut ApiStateAccessor<S>,
+) -> anyhow::Result<GenesisDocument> {
+    let runtime = Runtime::<S>::default();
+    let mut genesis = base.clone();
+
+    let mut addresses = BTreeMap::new();
+    for config in base.modules.values() {
+        collect_addresses::<S>(config, &mut addresses);
+    }
+    for address in extra_addresses {
+        addresses.insert(address.to_string(), address.clone());
+    }
+
+    // Live bonds, by owner, keyed like `addresses`.
+    let mut bonds: BTreeMap<String, u64> = BTreeMap::new();
+
+    let sequencer_registry = module(&mut genesis, "sequencer_registry")?;
+    let da_address: <S::Da as DaSpec>::Address =
+        serde_json::from_value(sequencer_registry["seq_da_address"].clone())
+            .context("Invalid `seq_da_address` in the sequencer registry genesis")?;
+    let seq_rollup_address: S::Address =
+        serde_json::from_value(sequencer_registry["seq_rollup_address"].clone())
+            .context("Invalid `seq_rollup_address` in the sequencer registry genesis")?;
+    match runtime
+        .sequencer_registry
+        .get_sender_balance(&da_address, state)
+        .unwrap_infallible()
+    {
+        Some(bond) => {
+            sequencer_registry["seq_bond"] = json!(bond);
+            add_bond(&mut bonds, seq_rollup_address.to_string(), bond);
+        }
+        // Its bond is already back in its balance, where the new chain locks it again.
+        None => tracing::warn!(
+            %da_address,
+            "The genesis sequencer is no longer registered, keeping its genesis bond"
+        ),
+    }
+
+    let mut attesters = Vec::new();
+    let mut provers = Vec::new();
+    for (key, address) in &addresses {
+        let attester_bond = runtime
+            .attester_incentives
+            .get_bond_amount(address.clone(), Role::Attester, state)
+            .value;
+        let challenger_bond = runtime
+            .attester_incentives
+            .get_bond_amount(address.clone(), Role::Challenger, state)
+            .value;
+        let prover_bond = runtime
+            .prover_incentives
+            .get_bond_amount(address.clone(), state)
+            .value;
+
+        if attester_bond != 0 {
+            attesters.push(json!([address, attester_bond]));
+        }
+        if prover_bond != 0 {
+            provers.push(json!([address, prover_bond]));
+        }
+        for bond in [attester_bond, challenger_bond, prover_bond] {
+            add_bond(&mut bonds, key.clone(), bond);
+        }
+    }
+    module(&mut genesis, "attester_incentives")?["initial_attesters"] = json!(attesters);
+    module(&mut genesis, "prover_incentives")?["initial_provers"] = json!(provers);
+
+    let bank = module(&mut genesis, "bank")?;
+    let gas_token_id = sov_bank::config_gas_token_id();
+    bank["gas_token_config"]["address_and_balances"] =
+        balances(&runtime, gas_token_id, &addresses, &bonds, state);
+    if let Some(tokens) = bank["tokens"].as_array_mut() {
+        for token in tokens {
+            let token_id = token_id(token)?;
+            token["address_and_balances"] =
+                balances(&runtime, token_id, &addresses, &BTreeMap::new(), state);
+        }
+    }
+
+    let example = &runtime.example_module;
+    let example_module = module(&mut genesis, "example_module")?;
+    let owner = example.owner.get(state).unwrap_infallible();
+    if let Some(owner) = &owner {
+        example_module["owner"] = json!(owner);
+    }
+    // The owner may have been made a writer before receiving the ownership, but the genesis
+    // rejects owners listed as writers.
+    example_module["writers"] = addresses
+        .values()
+        .filter(|address| Some(*address) != owner.as_ref())
+        .filter(|address| {
+            example
+                .writers
+                .get(*address, state)
+                .unwrap_infallible()
+                .is_some()
+        })
+        .map(|address| json!(address))
+        .collect();
+    example_module["initial_value"] = json!(example.value.get(state).unwrap_infallible());
+    example_module["fee"] = json!(example.fee_config.get(state).unwrap_infallible());
+    example_module["registers"] = addresses
+        .values()
+        .filter_map(|address| {
+            let register = example.registers.get(address, state).unwrap_infallible()?;
+            (register != 0).then(|| json!([address, register]))
+        })
+        .collect();
+
+    Ok(genesis)
+}
+
+/// The non-zero balances of `token_id`, plus the bonds of their owners.
+fn balances<S: Spec>(
+    runtime: &Runtime<S>,
+    token_id: TokenId,
+    addresses: &BTreeMap<String, S::Address>,
+    bonds: &BTreeMap<String, u64>,
+    state: &mut ApiStateAccessor<S>,
+) -> Value {
+    addresses
+        .iter()
+        .filter_map(|(key, address)| {
+            let balance = runtime
+                .bank
+                .get_balance_of(address, token_id, state)
+                .unwrap_infallible()
+                .unwrap_or_default()
+                .saturating_add(bonds.get(key).copied().unwrap_or_default());
+            (balance != 0).then(|| json!([address, balance]))
+        })
+        .collect()
+}
+
+/// Fails, listing the missing amounts, unless the balances of every token of the exported
+/// `genesis` add up to its total supply in `state`. Bonds count as balances of their owners.
+pub fn check_total_supply<S: Spec>(
+    genesis: &GenesisDocument,
+    state: &mut ApiStateAccessor<S>,
+) -> anyhow::Result<()> {
+    let runtime = Runtime::<S>::default();
+    let Some(bank) = genesis.modules.get("bank") else {
+        bail!("The genesis has no `bank` module");
+    };
+
+    let mut missing = Vec::new();
+    missing.extend(missing_supply(
+        &runtime,
+        sov_bank::config_gas_token_id(),
+        &bank["gas_token_config"],
+        state,
+    ));
+    for token in bank["tokens"].as_array().into_iter().flatten() {
+        missing.extend(missing_supply(&runtime, token_id(token)?, token, state));
+    }
+
+    if !missing.is_empty() {
+        bail!(
+            "The exported balances do not add up to the total supply, pass the missing \
+             holders as extra addresses: {}",
+            missing.join("; ")
+        );
+    }
+    Ok(())
+}
+
+/// Describes how much of the total supply of `token_id` is missing from the
+/// `address_and_balances` of `token`, if any.
+fn missing_supply<S: Spec>(
+    runtime: &Runtime<S>,
+    token_id: TokenId,
+    token: &Value,
+    state: &mut ApiStateAccessor<S>,
+) -> Option<String> {
+    let total_supply = runtime
+        .bank
+        .get_total_supply_of(&token_id, state)
+        .unwrap_infallible()
+        .unwrap_or_default();
+    let exported = token["address_and_balances"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry[1].as_u64())
+        .fold(0u64, u64::saturating_add);
+
+    (exported != total_supply).then(|| {
+        format!(
+            "{} ({token_id}) has a total supply of {total_supply}, {exported} were exported",
+            token["token_name"]
+        )
+    })
+}
+
+fn token_id(token: &Value) -> anyhow::Result<TokenId> {
+    serde_json::from_value(token["token_id"].clone())
+        .with_context(|| format!("Invalid id for token {}", token["token_name"]))
+}
+
+fn add_bond(bonds: &mut BTreeMap<String, u64>, key: String, bond: u64) {
+    let total = bonds.entry(key).or_default();
+    *total = total.saturating_add(bond);
+}
+
+/// Collects every string of `value` that is a rollup address, keyed by its canonical form.
+fn collect_addresses<S: Spec>(value: &Value, addresses: &mut BTreeMap<String, S::Address>) {
+    match value {
+        Value::String(_) => {
+            if let Ok(address) = serde_json::from_value::<S::Address>(value.clone()) {
+                addresses.insert(address.to_string(), address);
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_addresses::<S>(item, addresses)),
+        Value::Object(fields) => fields
+            .values()
+            .for_each(|field| collect_addresses::<S>(field, addresses)),
+        _ => {}
+    }
+}
+
+fn module<'a>(genesis: &'a mut GenesisDocument, name: &str) -> anyhow::Result<&'a mut Value> {
+    genesis
+        .modules
+        .get_mut(name)
+        .with_context(|| format!("The genesis has no `{name}` module"))
+}
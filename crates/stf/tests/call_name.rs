@@ -0,0 +1,63 @@
+use stf_starter::runtime::RuntimeCall;
+
+type S = sov_test_utils::TestSpec;
+
+#[test]
+fn test_call_names() {
+    let pause = RuntimeCall::<S>::Governance(governance::CallMessage::PauseModule {
+        module: "bank".to_string(),
+    });
+    assert_eq!(pause.module_name(), "governance");
+    assert_eq!(pause.call_name(), Some("pause_module"));
+
+    let remove = RuntimeCall::<S>::Paymaster(paymaster::CallMessage::RemovePolicy);
+    assert_eq!(remove.module_name(), "paymaster");
+    assert_eq!(remove.call_name(), Some("remove_policy"));
+
+    let add = RuntimeCall::<S>::ExampleModule(example_module::CallMessage::Add(1));
+    assert_eq!(add.call_name(), Some("add"));
+}
+
+/// Every named call belongs to a module of the runtime, and is listed once.
+#[test]
+fn test_runtime_calls() {
+    let calls = RuntimeCall::<S>::CALL_NAMES;
+    for (index, (module, call)) in calls.iter().enumerate() {
+        assert!(RuntimeCall::<S>::MODULE_NAMES.contains(module));
+        assert!(!calls[..index].contains(&(module, call)), "{module}/{call}");
+    }
+}
+
+/// The names must match the JSON representation of the calls, which is what governance
+/// policies are written against.
+#[test]
+fn test_call_names_match_the_json_representation() {
+    let calls = [
+        RuntimeCall::<S>::ExampleModule(example_module::CallMessage::Evaluate(vec![])),
+        RuntimeCall::<S>::Governance(governance::CallMessage::SetUnregisteredCalls {
+            calls: vec![],
+        }),
+        RuntimeCall::<S>::SessionKeys(session_keys::CallMessage::Revoke {
+            session: sov_modules_api::CredentialId([0; 32]),
+        }),
+        RuntimeCall::<S>::Paymaster(paymaster::CallMessage::RemovePolicy),
+    ];
+
+    for call in calls {
+        let json = match call {
+            RuntimeCall::ExampleModule(ref call) => serde_json::to_value(call),
+            RuntimeCall::Governance(ref call) => serde_json::to_value(call),
+            RuntimeCall::SessionKeys(ref call) => serde_json::to_value(call),
+            RuntimeCall::Paymaster(ref call) => serde_json::to_value(call),
+            _ => unreachable!(),
+        }
+        .unwrap();
+        let name = match json {
+            serde_json::Value::String(name) => name,
+            serde_json::Value::Object(fields) => fields.into_keys().next().unwrap(),
+            other => panic!("Unexpected representation {other}"),
+        };
+        assert_eq!(call.call_name(), Some(name.as_str()));
+        assert!(RuntimeCall::<S>::CALL_NAMES.contains(&(call.module_name(), name.as_str())));
+    }
+}
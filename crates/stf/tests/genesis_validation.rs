@@ -0,0 +1,236 @@
+use std::path::Path;
+
+use serde_json::{json, Value};
+use stf_starter::genesis_config::{create_genesis_config, GenesisDocument, GenesisSource};
+use stf_starter::genesis_validation::validate_genesis;
+
+type S = sov_test_utils::TestSpec;
+
+const GENESIS_DIR: &str = "../../test-data/genesis/mock/";
+
+/// Copies the mock genesis into a temporary directory and applies every edit to its file.
+fn edited_genesis(edits: &[(&str, &dyn Fn(&mut Value))]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for entry in std::fs::read_dir(GENESIS_DIR).unwrap() {
+        let entry = entry.unwrap();
+        std::fs::copy(entry.path(), dir.path().join(entry.file_name())).unwrap();
+    }
+
+    for (name, edit) in edits {
+        let path = dir.path().join(name);
+        let mut value: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        edit(&mut value);
+        std::fs::write(&path, serde_json::to_string_pretty(&value).unwrap()).unwrap();
+    }
+
+    dir
+}
+
+fn issues_in(dir: &Path) -> Vec<(String, String)> {
+    validate_genesis::<S>(&GenesisSource::from_path(dir))
+        .unwrap_err()
+        .issues
+        .into_iter()
+        .map(|issue| {
+            (
+                issue
+                    .file
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned(),
+                issue.message,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_valid_genesis() {
+    validate_genesis::<S>(&GenesisSource::from_path(GENESIS_DIR)).unwrap();
+}
+
+#[test]
+fn test_every_problem_is_reported() {
+    let dir = edited_genesis(&[
+        // The sequencer bonds more than it holds.
+        ("sequencer_registry.json", &|config| {
+            config["seq_bond"] = json!(2_000_000_000u64)
+        }),
+        // Below the minimum bond of 1000 gas units at the initial base fee.
+        ("prover_incentives.json", &|config| {
+            config["initial_provers"][0][1] = json!(1)
+        }),
+        ("accounts.json", &|config| {
+            let first = config["accounts"][0].clone();
+            config["accounts"].as_array_mut().unwrap().push(first);
+        }),
+    ]);
+    // Not even JSON.
+    std::fs::write(dir.path().join("example_module.json"), "{").unwrap();
+
+    let issues = issues_in(dir.path());
+    let files: Vec<&str> = issues.iter().map(|(file, _)| file.as_str()).collect();
+
+    assert!(files.contains(&"example_module.json"), "{issues:?}");
+    assert!(files.contains(&"sequencer_registry.json"), "{issues:?}");
+    assert!(files.contains(&"prover_incentives.json"), "{issues:?}");
+    assert!(issues
+        .iter()
+        .any(|(file, message)| file == "accounts.json" && message.contains("Duplicate")));
+}
+
+#[test]
+fn test_addresses_are_compared_once_parsed() {
+    // Bech32 is case-insensitive, so the uppercase bonders still hold their bank balance.
+    let uppercase = |config: &mut Value, pointer: &str| {
+        let address = config.pointer_mut(pointer).unwrap();
+        *address = json!(address.as_str().unwrap().to_uppercase());
+    };
+    let dir = edited_genesis(&[
+        ("attester_incentives.json", &|config| {
+            uppercase(config, "/initial_attesters/0/0")
+        }),
+        ("prover_incentives.json", &|config| {
+            uppercase(config, "/initial_provers/0/0")
+        }),
+    ]);
+    validate_genesis::<S>(&GenesisSource::from_path(dir.path())).unwrap();
+
+    // The same holder spelled twice is a duplicate.
+    let dir = edited_genesis(&[("bank.json", &|config| {
+        let holders = config
+            .pointer_mut("/gas_token_config/address_and_balances")
+            .unwrap()
+            .as_array_mut()
+            .unwrap();
+        let mut first = holders[0].clone();
+        uppercase(&mut first, "/0");
+        holders.push(first);
+    })]);
+    let issues = issues_in(dir.path());
+    assert!(
+        issues
+            .iter()
+            .any(|(file, message)| file == "bank.json" && message.contains("Duplicate")),
+        "{issues:?}"
+    );
+}
+
+#[test]
+fn test_gas_token_id_cannot_be_reused() {
+    let gas_token_id = sov_bank::config_gas_token_id().to_string();
+    let dir = edited_genesis(&[("bank.json", &|config| {
+        config["tokens"] = json!([{
+            "token_name": "other-token",
+            "token_id": gas_token_id,
+            "address_and_balances": [],
+            "authorized_minters": []
+        }])
+    })]);
+
+    let issues = issues_in(dir.path());
+    assert!(
+        issues
+            .iter()
+            .any(|(file, message)| file == "bank.json" && message.contains("GAS_TOKEN_ID")),
+        "{issues:?}"
+    );
+}
+
+#[test]
+fn test_paused_modules_must_exist() {
+    let dir = edited_genesis(&[("governance.json", &|config| {
+        config["paused_modules"] = json!(["bank", "unknown"])
+    })]);
+
+    let issues = issues_in(dir.path());
+    assert_eq!(
+        issues,
+        [(
+            "governance.json".to_string(),
+            "Cannot pause \"unknown\", the runtime has no such module".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_unregistered_calls_must_exist() {
+    let dir = edited_genesis(&[("governance.json", &|config| {
+        config["unregistered_calls"] = json!([
+            { "module": "sequencer_registry", "call": "register" },
+            { "module": "unknown" },
+            { "module": "bank", "call": "register" },
+        ])
+    })]);
+
+    let issues = issues_in(dir.path());
+    assert_eq!(
+        issues,
+        [
+            (
+                "governance.json".to_string(),
+                "Cannot allow calls to \"unknown\", the runtime has no such module".to_string()
+            ),
+            (
+                "governance.json".to_string(),
+                "Cannot allow call \"register\" of \"bank\", the module has no such call"
+                    .to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_governance_cannot_start_paused() {
+    let dir = edited_genesis(&[("governance.json", &|config| {
+        config["paused_modules"] = json!(["governance"])
+    })]);
+
+    let issues = issues_in(dir.path());
+    assert_eq!(
+        issues,
+        [(
+            "governance.json".to_string(),
+            "Cannot pause \"governance\", it is needed to unpause the others".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_single_file_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let exported = GenesisDocument::read(&GenesisSource::from_path(GENESIS_DIR)).unwrap();
+
+    for name in ["genesis.json", "genesis.toml"] {
+        let path = dir.path().join(name);
+        exported.write_file(&path).unwrap();
+
+        let source = GenesisSource::from_path(&path);
+        validate_genesis::<S>(&source).unwrap();
+        create_genesis_config::<S>(&source).unwrap();
+    }
+}
+
+#[test]
+fn test_single_file_issues_name_the_module() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut document = GenesisDocument::read(&GenesisSource::from_path(GENESIS_DIR)).unwrap();
+    document.modules.remove("evm");
+    document
+        .modules
+        .insert("unknown_module".to_string(), json!({}));
+    document.modules.get_mut("sequencer_registry").unwrap()["seq_bond"] = json!(2_000_000_000u64);
+    let path = dir.path().join("genesis.toml");
+    document.write_file(&path).unwrap();
+
+    let issues = issues_in(&path);
+    assert!(issues.iter().all(|(file, _)| file == "genesis.toml"));
+    for expected in ["unknown_module", "[evm]", "[sequencer_registry]"] {
+        assert!(
+            issues.iter().any(|(_, message)| message.contains(expected)),
+            "{expected} not in {issues:?}"
+        );
+    }
+}
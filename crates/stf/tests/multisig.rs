@@ -0,0 +1,103 @@
+use sov_modules_api::{CryptoSpec, PrivateKey, PublicKey, Spec};
+use stf_starter::multisig::{signing_message, CoSignature, Multisig, MultisigProof};
+
+type S = sov_test_utils::TestSpec;
+type Key = <<S as Spec>::CryptoSpec as CryptoSpec>::PrivateKey;
+type Hasher = <<S as Spec>::CryptoSpec as CryptoSpec>::Hasher;
+
+const TX: &[u8] = b"a serialized transaction";
+
+fn setup(threshold: u8) -> (Vec<Key>, Multisig<S>) {
+    let keys: Vec<Key> = (0..3).map(|_| Key::generate()).collect();
+    let multisig = Multisig {
+        threshold,
+        public_keys: keys.iter().map(|key| key.pub_key()).collect(),
+    };
+    (keys, multisig)
+}
+
+fn co_sign(keys: &[Key], signers: &[u8], tx: &[u8]) -> Vec<CoSignature<S>> {
+    signers
+        .iter()
+        .map(|signer| CoSignature {
+            signer: *signer,
+            signature: keys[usize::from(*signer)].sign(&signing_message::<S>(tx)),
+        })
+        .collect()
+}
+
+#[test]
+fn test_threshold_reached() {
+    let (keys, multisig) = setup(2);
+    let submitter = keys[0].pub_key().credential_id::<Hasher>();
+
+    let proof = MultisigProof {
+        multisig: multisig.clone(),
+        co_signatures: co_sign(&keys, &[2], TX),
+    };
+
+    assert_eq!(
+        proof.verify(TX, &submitter).unwrap(),
+        multisig.credential_id()
+    );
+}
+
+#[test]
+fn test_threshold_not_reached() {
+    let (keys, multisig) = setup(3);
+    let submitter = keys[0].pub_key().credential_id::<Hasher>();
+
+    let proof = MultisigProof {
+        multisig,
+        co_signatures: co_sign(&keys, &[1], TX),
+    };
+    assert!(proof.verify(TX, &submitter).is_err());
+}
+
+#[test]
+fn test_invalid_co_signatures() {
+    let (keys, multisig) = setup(2);
+    let submitter = keys[0].pub_key().credential_id::<Hasher>();
+
+    // The submitter cannot count twice.
+    let proof = MultisigProof {
+        multisig: multisig.clone(),
+        co_signatures: co_sign(&keys, &[0], TX),
+    };
+    assert!(proof.verify(TX, &submitter).is_err());
+
+    // Signatures over another transaction are rejected.
+    let proof = MultisigProof {
+        multisig: multisig.clone(),
+        co_signatures: co_sign(&keys, &[1], b"another transaction"),
+    };
+    assert!(proof.verify(TX, &submitter).is_err());
+
+    // So are unknown signers.
+    let proof = MultisigProof {
+        multisig: multisig.clone(),
+        co_signatures: vec![CoSignature {
+            signer: 3,
+            signature: keys[1].sign(&signing_message::<S>(TX)),
+        }],
+    };
+    assert!(proof.verify(TX, &submitter).is_err());
+
+    // And submitters outside of the multisig.
+    let outsider = Key::generate().pub_key().credential_id::<Hasher>();
+    let proof = MultisigProof {
+        multisig,
+        co_signatures: co_sign(&keys, &[1], TX),
+    };
+    assert!(proof.verify(TX, &outsider).is_err());
+}
+
+#[test]
+fn test_credential_depends_on_threshold() {
+    let (_, multisig) = setup(2);
+    let other = Multisig {
+        threshold: 1,
+        ..multisig.clone()
+    };
+    assert_ne!(multisig.credential_id(), other.credential_id());
+}
@@ -0,0 +1,56 @@
+use sov_accounts::AccountData;
+use sov_modules_api::CredentialId;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::TransactionTestCase;
+use stf_starter::authentication::EvmAuth;
+use stf_starter::MockDaSpec;
+
+use super::{gas_balance, genesis, pre_signed, runner, S};
+
+/// The EVM genesis of the mock rollup, which funds the dev accounts below on chain 4321.
+const EVM_GENESIS: &str = include_str!("../../../../test-data/genesis/mock/evm.json");
+
+/// The first dev account of the EVM genesis.
+const SIGNER: &str = "f39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+
+/// A legacy transaction of `SIGNER` with nonce 0 and a gas price of 1 gwei, sending 1 wei to
+/// `0x3fe0233e6cf3c9753fcb7449987ec49c88adde71` on chain 4321.
+const TRANSFER: &str = "f86580843b9aca00825208943fe0233e6cf3c9753fcb7449987ec49c88adde71018082\
+    21e6a00e864f715efd0320b681a547313e0bf3e2856c99b5534e7c73a294a440be3c72a07c4dd0ee2add5f265df9\
+    f716f3f01c9c966d7a2871f9f94961a188a8ef8ae2b6";
+
+fn decode_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_evm_transactions_are_charged_to_the_mapped_account() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(1);
+    let user = genesis_config.additional_accounts[0].clone();
+
+    // The signer's credential is its address, left-padded to 32 bytes.
+    let mut credential_id = [0; 32];
+    credential_id[12..].copy_from_slice(&decode_hex(SIGNER));
+
+    let mut genesis = genesis(genesis_config, &user);
+    genesis.evm = serde_json::from_str(EVM_GENESIS).unwrap();
+    genesis.accounts.accounts.push(AccountData {
+        credential_id: CredentialId(credential_id),
+        address: user.address(),
+    });
+    let mut runner = runner(genesis);
+
+    let balance_before = gas_balance(&mut runner, &user.address());
+
+    runner.execute_transaction(TransactionTestCase {
+        input: pre_signed(EvmAuth::<S, MockDaSpec>::encode(decode_hex(TRANSFER)).unwrap()),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    // The gas of the Ethereum transaction is paid by the rollup account mapped to its signer.
+    assert!(gas_balance(&mut runner, &user.address()) < balance_before);
+}
@@ -0,0 +1,124 @@
+//! Tests running transactions through the rollup's own [`Runtime`], with its authenticators,
+//! capabilities and hooks.
+use example_module::ExampleModuleConfig;
+use governance::{default_unregistered_calls, GovernanceConfig, SequencerPolicy};
+use paymaster::PaymasterConfig;
+use sov_bank::{config_gas_token_id, Bank};
+use sov_evm::EvmConfig;
+use sov_mock_da::MockAddress;
+use sov_modules_api::macros::config_value;
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_modules_api::transaction::{PriorityFeeBips, Transaction, UnsignedTransaction};
+use sov_modules_api::{CryptoSpec, RawTx, Spec};
+use sov_modules_stf_blueprint::GenesisParams;
+use sov_test_utils::runtime::genesis::optimistic::{
+    HighLevelOptimisticGenesisConfig, MinimalOptimisticGenesisConfig,
+};
+use sov_test_utils::runtime::TestRunner;
+use sov_test_utils::{BatchTestCase, TestUser, TransactionType};
+use stf_starter::{GenesisConfig, Runtime, RuntimeCall};
+
+mod evm;
+mod multisig;
+mod paused_modules;
+mod sessions;
+mod sponsored;
+mod stats;
+mod unregistered;
+
+type S = sov_test_utils::TestSpec;
+
+type Key = <<S as Spec>::CryptoSpec as CryptoSpec>::PrivateKey;
+
+/// The maximum fee of the transactions signed with [`signed_tx`].
+const MAX_FEE: u64 = 100_000_000;
+
+type RuntimeRunner = TestRunner<Runtime<S>, S>;
+
+/// The genesis of the runtime for the accounts of `genesis_config`, with `admin` owning the
+/// example module and governing the runtime. The other modules start empty.
+fn genesis(
+    genesis_config: HighLevelOptimisticGenesisConfig<S>,
+    admin: &TestUser<S>,
+) -> GenesisConfig<S> {
+    let minimal: MinimalOptimisticGenesisConfig<S> = genesis_config.into();
+
+    GenesisConfig::new(
+        minimal.accounts,
+        (),
+        minimal.bank,
+        minimal.sequencer_registry,
+        minimal.attester_incentives,
+        minimal.prover_incentives,
+        ExampleModuleConfig {
+            owner: admin.address(),
+            writers: vec![],
+            initial_value: None,
+            fee: None,
+            registers: vec![],
+        },
+        GovernanceConfig {
+            governor: admin.address(),
+            paused_modules: vec![],
+            runtime_modules: RuntimeCall::<S>::MODULE_NAMES.map(String::from).to_vec(),
+            runtime_calls: RuntimeCall::<S>::runtime_calls(),
+            sequencer_policy: SequencerPolicy::default(),
+            unregistered_calls: default_unregistered_calls(),
+        },
+        (),
+        EvmConfig::default(),
+        (),
+        PaymasterConfig { sponsors: vec![] },
+        minimal.chain_state,
+        (),
+    )
+}
+
+fn runner(genesis: GenesisConfig<S>) -> RuntimeRunner {
+    TestRunner::new_with_genesis(GenesisParams { runtime: genesis }, Runtime::default())
+}
+
+/// The borsh encoding of `call`, signed by `key` with the given `nonce`.
+fn signed_tx(key: &Key, call: RuntimeCall<S>, nonce: u64) -> Vec<u8> {
+    let tx = Transaction::<S>::new_signed_tx(
+        key,
+        UnsignedTransaction::new(
+            borsh::to_vec(&call).unwrap(),
+            config_value!("CHAIN_ID"),
+            PriorityFeeBips::ZERO,
+            MAX_FEE,
+            nonce,
+            None,
+        ),
+    );
+    borsh::to_vec(&tx).unwrap()
+}
+
+/// A transaction already wrapped in the runtime's `Auth` envelope.
+fn pre_signed(tx: RawTx) -> TransactionType<Runtime<S>, S> {
+    TransactionType::PreSigned(tx)
+}
+
+/// Executes `txs` in a batch sent from a DA address that is not a registered sequencer.
+fn execute_unregistered_batch(
+    runner: &mut RuntimeRunner,
+    txs: Vec<TransactionType<Runtime<S>, S>>,
+) {
+    runner.execute_batch_with_sender(
+        BatchTestCase {
+            input: txs.into(),
+            assert: Box::new(|_result, _state| {}),
+        },
+        MockAddress::new([0xaa; 32]),
+    );
+}
+
+/// The gas token balance of `address`.
+fn gas_balance(runner: &mut RuntimeRunner, address: &<S as Spec>::Address) -> u64 {
+    runner.query_state(|state| {
+        Bank::<S>::default()
+            .get_balance_of(address, config_gas_token_id(), state)
+            .unwrap_infallible()
+            .unwrap_or_default()
+    })
+}
@@ -0,0 +1,49 @@
+use example_module::CallMessage;
+use sov_accounts::AccountData;
+use sov_modules_api::PrivateKey;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::TransactionTestCase;
+use stf_starter::authentication::MultisigAuth;
+use stf_starter::multisig::{signing_message, CoSignature, Multisig, MultisigProof};
+use stf_starter::{MockDaSpec, RuntimeCall};
+
+use super::{gas_balance, genesis, pre_signed, runner, signed_tx, Key, S};
+
+#[test]
+fn test_multisig_transactions_are_charged_to_the_multisig_account() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(1);
+    let user = genesis_config.additional_accounts[0].clone();
+
+    let keys: Vec<Key> = (0..2).map(|_| Key::generate()).collect();
+    let multisig = Multisig::<S> {
+        threshold: 2,
+        public_keys: keys.iter().map(|key| key.pub_key()).collect(),
+    };
+
+    // The multisig sends its transactions from the account of `user`.
+    let mut genesis = genesis(genesis_config, &user);
+    genesis.accounts.accounts.push(AccountData {
+        credential_id: multisig.credential_id(),
+        address: user.address(),
+    });
+    let mut runner = runner(genesis);
+
+    let tx = signed_tx(&keys[0], RuntimeCall::ExampleModule(CallMessage::Add(1)), 0);
+    let proof = MultisigProof {
+        multisig,
+        co_signatures: vec![CoSignature {
+            signer: 1,
+            signature: keys[1].sign(&signing_message::<S>(&tx)),
+        }],
+    };
+
+    let balance_before = gas_balance(&mut runner, &user.address());
+
+    runner.execute_transaction(TransactionTestCase {
+        input: pre_signed(MultisigAuth::<S, MockDaSpec>::encode(tx, &proof).unwrap()),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    assert!(gas_balance(&mut runner, &user.address()) < balance_before);
+}
@@ -0,0 +1,65 @@
+use example_module::ExampleModule;
+use governance::Governance;
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::{AsUser, BatchTestCase, TransactionTestCase};
+
+use super::{genesis, runner, S};
+
+#[test]
+fn test_paused_module_calls_are_rejected() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(2);
+    let governor = genesis_config.additional_accounts[0].clone();
+    let user = genesis_config.additional_accounts[1].clone();
+
+    let mut runner = runner(genesis(genesis_config, &governor));
+
+    let pause = |module: &str| {
+        governor.create_plain_message::<Governance<S>>(governance::CallMessage::PauseModule {
+            module: module.to_string(),
+        })
+    };
+
+    // Only the modules of the runtime can be paused.
+    runner.execute_transaction(TransactionTestCase {
+        input: pause("unknown"),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_reverted())),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: pause("example_module"),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    // The call is dropped during authentication, the module never runs.
+    runner.execute_batch(BatchTestCase {
+        input: vec![
+            user.create_plain_message::<ExampleModule<S>>(example_module::CallMessage::Add(1))
+        ]
+        .into(),
+        assert: Box::new(|_result, _state| {}),
+    });
+    let user_address = user.address();
+    runner.query_state(|state| {
+        let register = ExampleModule::<S>::default()
+            .registers
+            .get(&user_address, state)
+            .unwrap_infallible();
+        assert_eq!(register, None);
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(
+            governance::CallMessage::UnpauseModule {
+                module: "example_module".to_string(),
+            },
+        ),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(example_module::CallMessage::Add(1)),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+}
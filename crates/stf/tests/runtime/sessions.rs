@@ -0,0 +1,206 @@
+use example_module::{CallMessage, ExampleModule, FeeConfig, FeeRecipient};
+use session_keys::SessionKeys;
+use sov_bank::config_gas_token_id;
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_modules_api::{CryptoSpec, PrivateKey, PublicKey, RawTx, Spec};
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::{AsUser, BatchTestCase, TransactionTestCase};
+use stf_starter::authentication::{Auth, SessionAuth};
+use stf_starter::{MockDaSpec, RuntimeCall};
+
+use super::{gas_balance, genesis, pre_signed, runner, signed_tx, Key, MAX_FEE, S};
+
+type Hasher = <<S as Spec>::CryptoSpec as CryptoSpec>::Hasher;
+
+fn grant(session: sov_modules_api::CredentialId) -> session_keys::CallMessage {
+    session_keys::CallMessage::Grant {
+        session,
+        modules: vec!["example_module".to_string()],
+        spend_limit: 2 * MAX_FEE,
+        expires_at: 1_000,
+    }
+}
+
+#[test]
+fn test_third_parties_cannot_block_or_use_an_account() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(2);
+    let user = genesis_config.additional_accounts[0].clone();
+    let attacker = genesis_config.additional_accounts[1].clone();
+
+    let mut runner = runner(genesis(genesis_config, &user));
+
+    // The attacker grants the user's own key, and a session key of its own.
+    let user_credential = user.private_key().pub_key().credential_id::<Hasher>();
+    let session_key = Key::generate();
+    let session = session_key.pub_key().credential_id::<Hasher>();
+    for credential in [user_credential, session] {
+        runner.execute_transaction(TransactionTestCase {
+            input: attacker.create_plain_message::<SessionKeys<S>>(grant(credential)),
+            assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+        });
+    }
+
+    // The user still sends regular transactions with its key.
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::Add(1)),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    // The attacker's session key cannot act for the user, who did not grant it.
+    let balance_before = gas_balance(&mut runner, &user.address());
+    let tx = signed_tx(
+        &session_key,
+        RuntimeCall::ExampleModule(CallMessage::Add(1)),
+        0,
+    );
+    runner.execute_batch(BatchTestCase {
+        input: vec![pre_signed(
+            SessionAuth::<S, MockDaSpec>::encode(tx, &user.address()).unwrap(),
+        )]
+        .into(),
+        assert: Box::new(|_result, _state| {}),
+    });
+    assert_eq!(gas_balance(&mut runner, &user.address()), balance_before);
+}
+
+#[test]
+fn test_session_transactions_are_charged_to_the_granter() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(1);
+    let user = genesis_config.additional_accounts[0].clone();
+
+    let mut runner = runner(genesis(genesis_config, &user));
+
+    let session_key = Key::generate();
+    let session = session_key.pub_key().credential_id::<Hasher>();
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<SessionKeys<S>>(grant(session)),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    let balance_before = gas_balance(&mut runner, &user.address());
+    let tx = signed_tx(
+        &session_key,
+        RuntimeCall::ExampleModule(CallMessage::Add(1)),
+        0,
+    );
+    runner.execute_transaction(TransactionTestCase {
+        input: pre_signed(SessionAuth::<S, MockDaSpec>::encode(tx, &user.address()).unwrap()),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    // The granter paid the gas, which counts against the limit of the grant.
+    let paid = balance_before - gas_balance(&mut runner, &user.address());
+    assert!(paid > 0);
+    let user_address = user.address();
+    runner.query_state(|state| {
+        let grant = SessionKeys::<S>::default()
+            .grants
+            .get(&(user_address, session), state)
+            .unwrap_infallible()
+            .expect("The grant is active");
+        assert_eq!(grant.spent, paid);
+    });
+}
+
+#[test]
+fn test_session_keys_do_not_act_for_the_granter_outside_sessions() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(1);
+    let user = genesis_config.additional_accounts[0].clone();
+
+    let mut runner = runner(genesis(genesis_config, &user));
+
+    let session_key = Key::generate();
+    let session = session_key.pub_key().credential_id::<Hasher>();
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<SessionKeys<S>>(grant(session)),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    let tx = signed_tx(
+        &session_key,
+        RuntimeCall::ExampleModule(CallMessage::Add(1)),
+        0,
+    );
+    runner.execute_transaction(TransactionTestCase {
+        input: pre_signed(SessionAuth::<S, MockDaSpec>::encode(tx, &user.address()).unwrap()),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    // The same key, without the session envelope, is an account of its own.
+    let balance_before = gas_balance(&mut runner, &user.address());
+    let tx = signed_tx(
+        &session_key,
+        RuntimeCall::ExampleModule(CallMessage::Add(1)),
+        1,
+    );
+    runner.execute_batch(BatchTestCase {
+        input: vec![pre_signed(RawTx {
+            data: borsh::to_vec(&Auth::Mod(tx)).unwrap(),
+        })]
+        .into(),
+        assert: Box::new(|_result, _state| {}),
+    });
+
+    assert_eq!(gas_balance(&mut runner, &user.address()), balance_before);
+    let user_address = user.address();
+    runner.query_state(|state| {
+        let register = ExampleModule::<S>::default()
+            .registers
+            .get(&user_address, state)
+            .unwrap_infallible();
+        assert_eq!(register, Some(1));
+    });
+}
+
+#[test]
+fn test_module_fees_count_against_the_spend_limit() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(1);
+    let user = genesis_config.additional_accounts[0].clone();
+
+    let mut runner = runner(genesis(genesis_config, &user));
+
+    let session_key = Key::generate();
+    let session = session_key.pub_key().credential_id::<Hasher>();
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<ExampleModule<S>>(CallMessage::SetFee {
+            fee: Some(FeeConfig {
+                token_id: config_gas_token_id(),
+                amount: 1_000,
+                recipient: FeeRecipient::Burn,
+            }),
+        }),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+    runner.execute_transaction(TransactionTestCase {
+        input: user.create_plain_message::<SessionKeys<S>>(grant(session)),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    let balance_before = gas_balance(&mut runner, &user.address());
+    let tx = signed_tx(
+        &session_key,
+        RuntimeCall::ExampleModule(CallMessage::Add(1)),
+        0,
+    );
+    runner.execute_transaction(TransactionTestCase {
+        input: pre_signed(SessionAuth::<S, MockDaSpec>::encode(tx, &user.address()).unwrap()),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    // The fee is burned from the granter in the gas token, and counted with the gas.
+    let paid = balance_before - gas_balance(&mut runner, &user.address());
+    assert!(paid > 1_000);
+    let user_address = user.address();
+    runner.query_state(|state| {
+        let grant = SessionKeys::<S>::default()
+            .grants
+            .get(&(user_address, session), state)
+            .unwrap_infallible()
+            .expect("The grant is active");
+        assert_eq!(grant.spent, paid);
+    });
+}
@@ -0,0 +1,61 @@
+use example_module::CallMessage;
+use paymaster::{Paymaster, PaymasterConfig, SponsorConfig, SponsorPolicy};
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::TransactionTestCase;
+use stf_starter::authentication::SponsoredAuth;
+use stf_starter::{MockDaSpec, RuntimeCall};
+
+use super::{gas_balance, genesis, pre_signed, runner, signed_tx, MAX_FEE, S};
+
+const BUDGET: u64 = 10 * MAX_FEE;
+
+#[test]
+fn test_sponsored_transactions_are_charged_to_the_sponsor() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(2);
+    let sponsor = genesis_config.additional_accounts[0].clone();
+    let user = genesis_config.additional_accounts[1].clone();
+
+    let mut genesis = genesis(genesis_config, &sponsor);
+    genesis.paymaster = PaymasterConfig {
+        sponsors: vec![SponsorConfig {
+            sponsor: sponsor.address(),
+            policy: SponsorPolicy {
+                modules: vec!["example_module".to_string()],
+                users: vec![user.address()],
+                allow_all_users: false,
+                budget: BUDGET,
+            },
+        }],
+    };
+    let mut runner = runner(genesis);
+
+    let sponsor_balance = gas_balance(&mut runner, &sponsor.address());
+    let user_balance = gas_balance(&mut runner, &user.address());
+
+    let tx = signed_tx(
+        user.private_key(),
+        RuntimeCall::ExampleModule(CallMessage::Add(1)),
+        0,
+    );
+    runner.execute_transaction(TransactionTestCase {
+        input: pre_signed(SponsoredAuth::<S, MockDaSpec>::encode(tx, &sponsor.address()).unwrap()),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    // The sponsor paid the gas, deducted from its budget, and the user paid nothing.
+    let paid = sponsor_balance - gas_balance(&mut runner, &sponsor.address());
+    assert!(paid > 0);
+    assert_eq!(gas_balance(&mut runner, &user.address()), user_balance);
+
+    let sponsor_address = sponsor.address();
+    runner.query_state(|state| {
+        let policy = Paymaster::<S>::default()
+            .policies
+            .get(&sponsor_address, state)
+            .unwrap_infallible()
+            .expect("The policy is still registered");
+        assert_eq!(policy.budget, BUDGET - paid);
+    });
+}
@@ -0,0 +1,100 @@
+use example_module::ExampleModule;
+use governance::{Governance, SequencerFilter, SequencerPolicy};
+use rollup_stats::RollupStats;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::{AsUser, BatchTestCase, TransactionTestCase};
+
+use super::{genesis, runner, S};
+
+#[test]
+fn test_stats_are_collected_by_the_runtime_hooks() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(1);
+    let user = genesis_config.additional_accounts[0].clone();
+
+    let genesis = genesis(genesis_config, &user);
+    let sequencer = genesis.sequencer_registry.seq_da_address;
+    let mut runner = runner(genesis);
+
+    for _ in 0..2 {
+        runner.execute_transaction(TransactionTestCase {
+            input: user
+                .create_plain_message::<ExampleModule<S>>(example_module::CallMessage::Add(1)),
+            assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+        });
+    }
+
+    runner.query_state(|state| {
+        let stats = RollupStats::<S>::default();
+
+        // Every slot is stored under its rollup height.
+        let latest = stats
+            .query_latest_slot(state)
+            .expect("Slots were finalized");
+        assert_eq!(latest.batches, 1);
+        assert_eq!(latest.txs, 1);
+        assert!(latest.gas_used.iter().any(|gas| *gas > 0));
+
+        let previous = stats
+            .query_slot(latest.slot_number - 1, state)
+            .expect("The previous slot was finalized");
+        assert_eq!(previous.txs, 1);
+        assert_ne!(previous.state_root, latest.state_root);
+
+        let sequencer = stats
+            .query_sequencer(&sequencer, state)
+            .expect("The sequencer submitted batches");
+        assert_eq!(sequencer.batches_accepted, 2);
+        assert_eq!(sequencer.batches_rejected, 0);
+        assert_eq!(sequencer.txs, 2);
+    });
+}
+
+#[test]
+fn test_rejected_batches_are_recorded() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(1);
+    let governor = genesis_config.additional_accounts[0].clone();
+
+    let genesis = genesis(genesis_config, &governor);
+    let sequencer = genesis.sequencer_registry.seq_da_address;
+    let mut runner = runner(genesis);
+
+    runner.execute_transaction(TransactionTestCase {
+        input: governor.create_plain_message::<Governance<S>>(
+            governance::CallMessage::SetSequencerPolicy {
+                policy: SequencerPolicy {
+                    filter: SequencerFilter::Denylist(vec![sequencer.as_ref().to_vec()]),
+                    max_batches_per_slot: None,
+                },
+            },
+        ),
+        assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+    });
+
+    // `begin_batch_hook` rejects the batch, none of its transactions is applied.
+    runner.execute_batch(BatchTestCase {
+        input: vec![
+            governor.create_plain_message::<ExampleModule<S>>(example_module::CallMessage::Add(1))
+        ]
+        .into(),
+        assert: Box::new(|_result, _state| {}),
+    });
+
+    runner.query_state(|state| {
+        let stats = RollupStats::<S>::default();
+
+        let latest = stats
+            .query_latest_slot(state)
+            .expect("Slots were finalized");
+        assert_eq!(latest.batches, 0);
+        assert_eq!(latest.txs, 0);
+
+        let sequencer = stats
+            .query_sequencer(&sequencer, state)
+            .expect("The sequencer submitted batches");
+        assert_eq!(sequencer.batches_accepted, 1);
+        assert_eq!(sequencer.batches_rejected, 1);
+        assert_eq!(sequencer.txs, 1);
+    });
+}
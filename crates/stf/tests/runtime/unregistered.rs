@@ -0,0 +1,36 @@
+use example_module::{CallMessage, ExampleModule};
+use governance::UnregisteredCall;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::AsUser;
+
+use super::{execute_unregistered_batch, gas_balance, genesis, runner, S};
+
+#[test]
+fn test_unregistered_sequencers_only_submit_allowed_calls() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(1);
+    let user = genesis_config.additional_accounts[0].clone();
+
+    let mut genesis = genesis(genesis_config, &user);
+    genesis.governance.unregistered_calls = vec![UnregisteredCall {
+        module: "example_module".to_string(),
+        call: Some("add".to_string()),
+    }];
+    let mut runner = runner(genesis);
+
+    // `add` is allowed: the transaction is executed and its gas paid.
+    let balance_before = gas_balance(&mut runner, &user.address());
+    execute_unregistered_batch(
+        &mut runner,
+        vec![user.create_plain_message::<ExampleModule<S>>(CallMessage::Add(1))],
+    );
+    let balance_after_add = gas_balance(&mut runner, &user.address());
+    assert!(balance_after_add < balance_before);
+
+    // `sub` is not: the transaction is dropped during authentication.
+    execute_unregistered_batch(
+        &mut runner,
+        vec![user.create_plain_message::<ExampleModule<S>>(CallMessage::Sub(1))],
+    );
+    assert_eq!(gas_balance(&mut runner, &user.address()), balance_after_add);
+}
@@ -0,0 +1,118 @@
+use example_module::{CallMessage, ExampleModule, ExampleModuleConfig};
+use serde_json::json;
+use sov_bank::{config_gas_token_id, Bank};
+use sov_modules_api::prelude::UnwrapInfallible;
+use sov_modules_stf_blueprint::GenesisParams;
+use sov_test_utils::runtime::genesis::optimistic::HighLevelOptimisticGenesisConfig;
+use sov_test_utils::runtime::TestRunner;
+use sov_test_utils::{generate_optimistic_runtime, AsUser, TransactionTestCase};
+use stf_starter::genesis_config::{create_genesis_config, GenesisDocument, GenesisSource};
+use stf_starter::state_export::{check_total_supply, export_state};
+use stf_starter::Runtime;
+
+generate_optimistic_runtime!(ExportRuntime <= example_module: ExampleModule<S>);
+
+type S = sov_test_utils::TestSpec;
+
+const GENESIS_DIR: &str = "../../test-data/genesis/mock/";
+
+#[test]
+fn test_export_state() {
+    let genesis_config =
+        HighLevelOptimisticGenesisConfig::generate().add_accounts_with_default_balance(2);
+    let owner = genesis_config.additional_accounts[0].clone();
+    let user = genesis_config.additional_accounts[1].clone();
+
+    let genesis = GenesisConfig::from_minimal_config(
+        genesis_config.into(),
+        ExampleModuleConfig {
+            owner: owner.address(),
+            writers: vec![],
+            initial_value: None,
+            fee: None,
+            registers: vec![],
+        },
+    );
+    let mut runner =
+        TestRunner::new_with_genesis(genesis.into_genesis_params(), ExportRuntime::default());
+
+    for message in [CallMessage::SetValue(7), CallMessage::Add(3)] {
+        runner.execute_transaction(TransactionTestCase {
+            input: owner.create_plain_message::<ExampleModule<S>>(message),
+            assert: Box::new(|result, _state| assert!(result.tx_receipt.is_successful())),
+        });
+    }
+
+    let base = GenesisDocument::read(&GenesisSource::from_path(GENESIS_DIR)).unwrap();
+    let (owner, user) = (owner.address(), user.address());
+    runner.query_state(|state| {
+        let exported = export_state::<S>(&base, &[owner.clone(), user.clone()], state).unwrap();
+
+        let example_module = &exported.modules["example_module"];
+        assert_eq!(example_module["owner"], json!(owner));
+        assert_eq!(example_module["initial_value"], json!(7));
+        assert_eq!(example_module["registers"], json!([[owner, 3]]));
+        assert_eq!(example_module["writers"], json!([]));
+
+        let balances = &exported.modules["bank"]["gas_token_config"]["address_and_balances"];
+        for address in [&owner, &user] {
+            let balance = Bank::<S>::default()
+                .get_balance_of(address, config_gas_token_id(), state)
+                .unwrap_infallible()
+                .unwrap();
+            assert!(
+                balances
+                    .as_array()
+                    .unwrap()
+                    .contains(&json!([address, balance])),
+                "{address} with {balance} not in {balances}"
+            );
+        }
+
+        // The sequencer, attester and challenger of the test runtime are not in the base
+        // genesis, so their balances are missing.
+        let error = check_total_supply::<S>(&exported, state).unwrap_err();
+        assert!(error.to_string().contains("missing holders"), "{error}");
+    });
+}
+
+/// The sum of the gas token balances of `genesis`.
+fn gas_supply(genesis: &GenesisDocument) -> u64 {
+    genesis.modules["bank"]["gas_token_config"]["address_and_balances"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry[1].as_u64().unwrap())
+        .sum()
+}
+
+#[test]
+fn test_bonds_are_exported_without_changing_the_supply() {
+    let source = GenesisSource::from_path(GENESIS_DIR);
+    let base = GenesisDocument::read(&source).unwrap();
+    let mut runner = TestRunner::new_with_genesis(
+        GenesisParams {
+            runtime: create_genesis_config::<S>(&source).unwrap(),
+        },
+        Runtime::<S>::default(),
+    );
+
+    runner.query_state(|state| {
+        let exported = export_state::<S>(&base, &[], state).unwrap();
+
+        // Nothing happened since genesis, so the bonds are the genesis ones.
+        for (module, field) in [
+            ("attester_incentives", "initial_attesters"),
+            ("prover_incentives", "initial_provers"),
+            ("sequencer_registry", "seq_bond"),
+        ] {
+            let bonds = &exported.modules[module][field];
+            assert_eq!(bonds, &base.modules[module][field]);
+            assert_ne!(bonds, &json!([]));
+        }
+        assert_ne!(exported.modules["sequencer_registry"]["seq_bond"], json!(0));
+
+        assert_eq!(gas_supply(&exported), gas_supply(&base));
+        check_total_supply::<S>(&exported, state).unwrap();
+    });
+}